@@ -0,0 +1,183 @@
+// Known puzzle answers, read from a small TOML-style file:
+//
+//     [day1]
+//     part1 = 1162
+//     part2 = "some text"
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::fmt;
+use std::fs;
+use std::io;
+
+pub const DEFAULT_FILE: &str = "data/answers.toml";
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    #[test]
+    fn numbers_and_strings() {
+        let input = "# comment\n[day1]\npart1 = 1162\npart2 = \"ABCD\"\n\n[day12]\npart2 = 96528 # trailing\n";
+        let answers = Answers::parse(input).expect("parsing failed!");
+
+        assert_eq!(Some("1162"), answers.get(1, 1));
+        assert_eq!(Some("ABCD"), answers.get(1, 2));
+        assert_eq!(None, answers.get(12, 1));
+        assert_eq!(Some("96528"), answers.get(12, 2));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(AnswersError::NoSection(1), Answers::parse("part1 = 1").unwrap_err());
+        assert_eq!(AnswersError::Syntax(2), Answers::parse("[day1]\npart1 1").unwrap_err());
+        assert_eq!(AnswersError::Syntax(1), Answers::parse("[dayx]").unwrap_err());
+        assert_eq!(AnswersError::Syntax(2), Answers::parse("[day1]\npart3 = 1").unwrap_err());
+        assert_eq!(AnswersError::Syntax(2), Answers::parse("[day1]\npart1 = \"open").unwrap_err());
+    }
+}
+
+#[cfg(test)]
+mod test_check {
+    use super::*;
+
+    #[test]
+    fn statuses() {
+        assert_eq!(Status::Pass, check(Some("12"), "12"));
+        assert_eq!(Status::Fail(String::from("12")), check(Some("12"), "13"));
+        assert_eq!(Status::Unknown, check(None, "13"));
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum AnswersError {
+    Io(String, io::ErrorKind),
+    NoSection(usize),
+    Syntax(usize),
+
+    // An answer given for an example that has no input file.
+    NoExample(String)
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnswersError::Io(file, kind) => write!(f, "could not read {}: {:?}", file, kind),
+            AnswersError::NoSection(line) => write!(f, "line {}: answer given before any [dayN] section", line),
+            AnswersError::Syntax(line) => write!(f, "line {}: expected [dayN] or partN = value", line),
+            AnswersError::NoExample(name) => write!(f, "answers given for '{}', which has no input file", name)
+        }
+    }
+}
+
+// Result of checking a part's result against its known answer.
+#[derive(PartialEq, Eq, Debug)]
+pub enum Status {
+    Pass,
+    Fail(String),
+    Unknown,
+
+    // The part returned an error (or the input couldn't be parsed).
+    Failed(String),
+
+    // The part didn't finish within its time limit.
+    Timeout
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "PASS"),
+            Status::Fail(expected) => write!(f, "FAIL (expected {})", expected),
+            Status::Unknown => write!(f, "UNKNOWN"),
+            Status::Failed(e) => write!(f, "FAILED ({})", e),
+            Status::Timeout => write!(f, "TIMEOUT")
+        }
+    }
+}
+
+pub fn check(expected: Option<&str>, result: &str) -> Status {
+    match expected {
+        Some(e) if e == result => Status::Pass,
+        Some(e) => Status::Fail(e.to_string()),
+        None => Status::Unknown
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct Answers {
+    answers: HashMap<(u32, u32), String>
+}
+
+impl Answers {
+    pub fn load(file: &str) -> Result<Answers, AnswersError> {
+        let s = fs::read_to_string(file).map_err(|e| AnswersError::Io(file.to_string(), e.kind()))?;
+        Answers::parse(&s)
+    }
+
+    pub fn parse(s: &str) -> Result<Answers, AnswersError> {
+        let answers = parse_sections(s, |name| name.strip_prefix("day")?.parse().ok())?;
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(day, part)).map(|s| s.as_str())
+    }
+}
+
+// Parses answers into a map by section and part. The section function turns
+// a section's name into its key, or None if it isn't a valid name.
+pub(crate) fn parse_sections<K: Clone + Eq + Hash>(s: &str, section: impl Fn(&str) -> Option<K>) -> Result<HashMap<(K, u32), String>, AnswersError> {
+    let mut answers = HashMap::new();
+    let mut key = None;
+
+    for (i, line) in s.lines().enumerate() {
+        let n = i + 1;
+
+        // Strip comments, but not from inside a quoted value.
+        let line = match line.find('#') {
+            Some(c) if line[..c].matches('"').count() % 2 == 0 => &line[..c],
+            _ => line
+        };
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            key = Some(section(name).ok_or(AnswersError::Syntax(n))?);
+            continue;
+        }
+
+        let mut split = line.splitn(2, '=');
+        let name = split.next().ok_or(AnswersError::Syntax(n))?.trim();
+        let value = split.next().ok_or(AnswersError::Syntax(n))?.trim();
+
+        let part = match name {
+            "part1" => 1,
+            "part2" => 2,
+            _ => return Err(AnswersError::Syntax(n))
+        };
+
+        let value = if let Some(quoted) = value.strip_prefix('"') {
+            quoted.strip_suffix('"').ok_or(AnswersError::Syntax(n))?
+        }
+        else {
+            value
+        };
+
+        let key = key.as_ref().ok_or(AnswersError::NoSection(n))?;
+        answers.insert((key.clone(), part), value.to_string());
+    }
+
+    Ok(answers)
+}
+
+// The known answer for a part, from the default answers file.
+// For use in tests, so panics if there isn't one.
+pub fn expected(day: u32, part: u32) -> String {
+    let answers = Answers::load(DEFAULT_FILE).unwrap_or_else(|e| panic!("{}", e));
+    let answer = answers.get(day, part).unwrap_or_else(|| panic!("No answer for day {} part {} in {}", day, part, DEFAULT_FILE));
+    answer.to_string()
+}
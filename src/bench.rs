@@ -0,0 +1,193 @@
+// Repeated timing of puzzle parts, for comparing changes.
+
+use std::fmt::{Display, Write};
+use std::time::{Duration, Instant};
+
+use crate::answers::{self, Status};
+use crate::memory;
+
+#[cfg(test)]
+mod test_stats {
+    use super::*;
+
+    #[test]
+    fn single() {
+        let s = Stats::from_samples(&[0.5]);
+        assert_eq!(1, s.iterations);
+        assert_eq!(0.5, s.min);
+        assert_eq!(0.5, s.median);
+        assert_eq!(0.5, s.mean);
+        assert_eq!(0.0, s.std_dev);
+        assert_eq!(2.0, s.per_sec);
+    }
+
+    #[test]
+    fn odd() {
+        let s = Stats::from_samples(&[3.0, 1.0, 2.0]);
+        assert_eq!(3, s.iterations);
+        assert_eq!(1.0, s.min);
+        assert_eq!(2.0, s.median);
+        assert_eq!(2.0, s.mean);
+        assert_eq!(1.0, s.std_dev);
+        assert_eq!(0.5, s.per_sec);
+    }
+
+    #[test]
+    fn even() {
+        let s = Stats::from_samples(&[4.0, 1.0, 2.0, 1.0]);
+        assert_eq!(1.0, s.min);
+        assert_eq!(1.5, s.median);
+        assert_eq!(2.0, s.mean);
+        assert_eq!(2.0_f64.sqrt(), s.std_dev);
+    }
+}
+
+#[cfg(test)]
+mod test_bench {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn iterations() {
+        let calls = Cell::new(0);
+        let b = Bench { warmup: 2, limit: Limit::Iterations(5) };
+
+        let (result, stats) = b.run(&|| { calls.set(calls.get() + 1); 42 });
+
+        assert_eq!(42, result);
+        assert_eq!(5, stats.iterations);
+        assert_eq!(7, calls.get());
+    }
+
+    #[test]
+    fn time_runs_at_least_once() {
+        let b = Bench { warmup: 0, limit: Limit::Time(Duration::from_secs(0)) };
+
+        let (_, stats) = b.run(&|| 1);
+
+        assert_eq!(1, stats.iterations);
+    }
+}
+
+// When to stop taking timed samples.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Limit {
+    Iterations(u32),
+    Time(Duration)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Bench {
+    pub warmup: u32,
+    pub limit: Limit
+}
+
+impl Default for Bench {
+    fn default() -> Bench {
+        Bench { warmup: 3, limit: Limit::Time(Duration::from_secs(1)) }
+    }
+}
+
+// Summary of a set of timings, all in seconds.
+#[derive(Debug)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub per_sec: f64
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[f64]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n/2 - 1] + sorted[n/2]) / 2.0
+        }
+        else {
+            sorted[n/2]
+        };
+
+        let mean = sorted.iter().sum::<f64>() / n as f64;
+
+        // Sample standard deviation.
+        let std_dev = if n > 1 {
+            let sum_sq: f64 = sorted.iter().map(|s| (s - mean) * (s - mean)).sum();
+            (sum_sq / (n - 1) as f64).sqrt()
+        }
+        else {
+            0.0
+        };
+
+        Stats { iterations: n, min: sorted[0], median, mean, std_dev, per_sec: 1.0 / mean }
+    }
+}
+
+impl Bench {
+    // Runs f for the warm-up iterations, then times it until the limit is reached.
+    // Returns the result of the last run.
+    pub fn run<T>(&self, f: &dyn Fn() -> T) -> (T, Stats) {
+        for _ in 0..self.warmup {
+            f();
+        }
+
+        let mut samples = Vec::new();
+        let start = Instant::now();
+
+        loop {
+            let sample_start = Instant::now();
+            let result = f();
+            samples.push(sample_start.elapsed().as_secs_f64());
+
+            let done = match self.limit {
+                Limit::Iterations(n) => samples.len() >= n as usize,
+                Limit::Time(t) => start.elapsed() >= t
+            };
+
+            if done {
+                return (result, Stats::from_samples(&samples));
+            }
+        }
+    }
+}
+
+// Prints the column headings for the rows printed by part.
+pub fn header() {
+    println!("{:18} {:25} {:<20} {:>10} {:>10} {:>10} {:>10} {:>10} {:>6}",
+             "", "", "", "min", "median", "mean", "std dev", "iter/s", "n");
+}
+
+// As aoc::part, but benchmarks f and prints statistics instead of a single timing.
+// Allocations are counted for a single run, before benchmarking.
+// Returns the status and the median time in seconds.
+pub fn part<T: Display, E: Display>(out: &mut String, s: &str, descr: &str, f: &dyn Fn() -> Result<T, E>, expected: Option<&str>, bench: &Bench) -> (Status, f64) {
+    let (first, mem) = memory::measure(f);
+    let mem = if memory::enabled() { format!("  {}", mem) } else { String::new() };
+
+    // Don't benchmark a part that fails.
+    if let Err(e) = first {
+        let _ = writeln!(out, "{:18} {:25} {:<20} {:>10} {:>10} {:>10} {:>10} {:>10} {:>6}  {}", s, descr, "FAILED", "", "", "", "", "", "", e);
+        return (Status::Failed(e.to_string()), 0.0);
+    }
+
+    let (result, stats) = bench.run(f);
+
+    match result {
+        Ok(result) => {
+            let status = answers::check(expected, &result.to_string());
+
+            let _ = writeln!(out, "{:18} {:25} {:<20} {:>10.6} {:>10.6} {:>10.6} {:>10.6} {:>10.1} {:>6}{}  {}",
+                             s, descr, result, stats.min, stats.median, stats.mean, stats.std_dev, stats.per_sec, stats.iterations, mem, status);
+
+            (status, stats.median)
+        },
+        Err(e) => {
+            let _ = writeln!(out, "{:18} {:25} {:<20} {:>10} {:>10} {:>10} {:>10} {:>10} {:>6}  {}", s, descr, "FAILED", "", "", "", "", "", "", e);
+            (Status::Failed(e.to_string()), stats.median)
+        }
+    }
+}
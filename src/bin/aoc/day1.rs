@@ -1,18 +1,21 @@
 // Advent of Code 2021
 // Day 1
 
-use aoc::data;
+use aoc::{data, Day, Error, Solution};
 
 #[cfg(test)]
 mod test_puzzles {
+    use super::*;
+    use aoc::answers;
+
     #[test]
     fn part1() {
-        assert_eq!(1162, super::part1());
+        assert_eq!(answers::expected(1, 1), Day1::part1(&aoc::input::<Day1>()).expect("part1 failed!").to_string());
     }
 
     #[test]
     fn part2() {
-        assert_eq!(1190, super::part2());
+        assert_eq!(answers::expected(1, 2), Day1::part2(&aoc::input::<Day1>()).expect("part2 failed!").to_string());
     }
 }
 
@@ -21,39 +24,8 @@ mod test_examples {
     use super::*;
 
     #[test]
-    fn part1() {
-        let input = vec![
-            199,
-            200,
-            208,
-            210,
-            200,
-            207,
-            240,
-            269,
-            260,
-            263,
-        ];
-
-        assert_eq!(7, num_increases(&input));
-    }
-
-    #[test]
-    fn part2() {
-        let input = vec![
-            199,
-            200,
-            208,
-            210,
-            200,
-            207,
-            240,
-            269,
-            260,
-            263,
-        ];
-
-        assert_eq!(5, num_increases_sliding_window(&input));
+    fn answers() {
+        aoc::check_examples::<Day1>();
     }
 }
 
@@ -199,18 +171,28 @@ fn num_increases_sliding_window(report: &[u32]) -> u32 {
     num_increases(&sums)
 }
 
-fn part1() -> u32 {
-    let input = data::get("data/day1.txt");
+struct Day1;
 
-    num_increases(&input)
-}
+pub const DAY: Day = Day::new::<Day1>();
 
-fn part2() -> u32 {
-    let input = data::get("data/day1.txt");
+impl Solution for Day1 {
+    type Input = Vec<u32>;
+    type Output1 = u32;
+    type Output2 = u32;
 
-    num_increases_sliding_window(&input)
-}
+    const DAY: u32 = 1;
+    const PART1: &'static str = "depth increases";
+    const PART2: &'static str = "with sliding window";
 
-fn main() {
-    aoc::solution!(1, "depth increases", "with sliding window");
+    fn parse(input: &str) -> Result<Vec<u32>, Error> {
+        Ok(data::from_lines(input)?)
+    }
+
+    fn part1(input: &Vec<u32>) -> Result<u32, Error> {
+        Ok(num_increases(input))
+    }
+
+    fn part2(input: &Vec<u32>) -> Result<u32, Error> {
+        Ok(num_increases_sliding_window(input))
+    }
 }
\ No newline at end of file
@@ -1,13 +1,22 @@
 // Advent of Code 2021
 // Day 10
 
-use aoc::data;
+
+use aoc::{data, Context, Day, Error, Solution};
 
 #[cfg(test)]
 mod test_puzzles {
+    use super::*;
+    use aoc::answers;
+
     #[test]
     fn part1() {
-        assert_eq!(240123, super::part1());
+        assert_eq!(answers::expected(10, 1), Day10::part1(&aoc::input::<Day10>()).expect("part1 failed!").to_string());
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(answers::expected(10, 2), Day10::part2(&aoc::input::<Day10>()).expect("part2 failed!").to_string());
     }
 }
 
@@ -15,6 +24,17 @@ mod test_puzzles {
 mod test_examples {
     use super::*;
 
+    #[test]
+    fn answers() {
+        aoc::check_examples::<Day10>();
+    }
+
+    #[test]
+    fn nothing_to_complete() {
+        let input = Day10::parse("()\n").expect("parsing failed!");
+        assert_eq!("no incomplete lines to score", Day10::part2(&input).unwrap_err().to_string());
+    }
+
     #[test]
     fn part1() {
         let input = "{([(<{}[<>[]}>{[]{[(<()>";
@@ -218,7 +238,7 @@ fn autocomplete(s: &str) -> Option<String> {
 
     // We've got a stack - map each entry onto the expected
     // RHS character and reverse to form the completion string!
-    return Some(stack.iter().map(|c| expected_rhs(*c)).rev().collect());
+    Some(stack.iter().map(|c| expected_rhs(*c)).rev().collect())
 }
 
 // Given a LHS character, returns the expected RHS character.
@@ -286,34 +306,46 @@ fn syntax_score(e: ChunkParseError) -> u32 {
     }
 }
 
-fn part1() -> u32 {
-    let input: Vec<String> = data::get("data/day10.txt");
-    let mut score = 0;
-    for i in input {
-        let r = parse(&i);
-        if let Err(e) = r {
-            score += syntax_score(e);
-        }
-    }
+struct Day10;
 
-    score
-}
+pub const DAY: Day = Day::new::<Day10>();
+
+impl Solution for Day10 {
+    type Input = Vec<String>;
+    type Output1 = u32;
+    type Output2 = u64;
+
+    const DAY: u32 = 10;
+    const PART1: &'static str = "syntax error score";
+    const PART2: &'static str = "autocomplete score";
+
+    fn parse(input: &str) -> Result<Vec<String>, Error> {
+        Ok(data::from_lines(input)?)
+    }
 
-fn part2() -> u64 {
-    let input: Vec<String> = data::get("data/day10.txt");
-    let mut scores = Vec::new();
-    for i in input {
-        let a = autocomplete(&i);
-        if let Some(score) = a {
-            scores.push(autocomplete_score(&score))
+    fn part1(input: &Vec<String>) -> Result<u32, Error> {
+        let mut score = 0;
+        for i in input {
+            let r = parse(i);
+            if let Err(e) = r {
+                score += syntax_score(e);
+            }
         }
+
+        Ok(score)
     }
 
-    // Sort scores, select the middle.
-    scores.sort_unstable();
-    scores[scores.len() / 2]
-}
+    fn part2(input: &Vec<String>) -> Result<u64, Error> {
+        let mut scores = Vec::new();
+        for i in input {
+            let a = autocomplete(i);
+            if let Some(score) = a {
+                scores.push(autocomplete_score(&score))
+            }
+        }
 
-fn main() {
-    aoc::solution!(10, "syntax error score", "autocomplete score");
+        // Sort scores, select the middle.
+        scores.sort_unstable();
+        scores.get(scores.len() / 2).copied().context("no incomplete lines to score")
+    }
 }
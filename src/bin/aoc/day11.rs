@@ -1,20 +1,24 @@
 // Advent of Code 2021
 // Day 11
 
-use std::slice::Iter;
 use std::collections::HashSet;
+
+use aoc::{data, Day, Error, Solution};
 use aoc::drawing::*;
 
 #[cfg(test)]
 mod test_puzzles {
+    use super::*;
+    use aoc::answers;
+
     #[test]
     fn part1() {
-        assert_eq!(1652, super::part1());
+        assert_eq!(answers::expected(11, 1), Day11::part1(&aoc::input::<Day11>()).expect("part1 failed!").to_string());
     }
 
     #[test]
     fn part2() {
-        assert_eq!(220, super::part2());
+        assert_eq!(answers::expected(11, 2), Day11::part2(&aoc::input::<Day11>()).expect("part2 failed!").to_string());
     }
 }
 
@@ -23,41 +27,8 @@ mod test_examples {
     use super::*;
 
     #[test]
-    fn part1() {
-        let input = vec![
-            "5483143223",
-            "2745854711",
-            "5264556173",
-            "6141336146",
-            "6357385478",
-            "4167524645",
-            "2176841721",
-            "6882881134",
-            "4846848554",
-            "5283751526",
-        ];
-
-        let mut grid = get_grid(&mut input.iter());
-        assert_eq!(1656, total_flashes(&mut grid));
-    }
-
-    #[test]
-    fn part2() {
-        let input = vec![
-            "5483143223",
-            "2745854711",
-            "5264556173",
-            "6141336146",
-            "6357385478",
-            "4167524645",
-            "2176841721",
-            "6882881134",
-            "4846848554",
-            "5283751526",
-        ];
-
-        let mut grid = get_grid(&mut input.iter());
-        assert_eq!(195, first_synchronized_flash(&mut grid));
+    fn answers() {
+        aoc::check_examples::<Day11>();
     }
 }
 
@@ -67,26 +38,26 @@ mod test_step {
 
     #[test]
     fn simple() {
-        let input = vec![
+        let input = [
             "000",
             "000",
             "000"
         ];
 
-        let mut grid = get_grid(&mut input.iter());
+        let mut grid = data::digit_grid(&input.join("\n")).expect("parsing failed!");
         assert_eq!(0, step(&mut grid));
         assert_eq!(9, grid.count(&|v| v == 1));
     }
 
     #[test]
     fn one_flash_middle() {
-        let input = vec![
+        let input = [
             "000",
             "090",
             "000"
         ];
 
-        let mut grid = get_grid(&mut input.iter());
+        let mut grid = data::digit_grid(&input.join("\n")).expect("parsing failed!");
         assert_eq!(1, step(&mut grid));
         assert_eq!(0, grid.get(&Point::new(1, 1)));
         assert_eq!(8, grid.count(&|v| v == 2));
@@ -94,13 +65,13 @@ mod test_step {
 
     #[test]
     fn one_flash_edge() {
-        let input = vec![
+        let input = [
             "090",
             "000",
             "000"
         ];
 
-        let mut grid = get_grid(&mut input.iter());
+        let mut grid = data::digit_grid(&input.join("\n")).expect("parsing failed!");
         assert_eq!(1, step(&mut grid));
         assert_eq!(0, grid.get(&Point::new(1, 0)));
         assert_eq!(5, grid.count(&|v| v == 2));
@@ -113,13 +84,13 @@ mod test_step {
 
     #[test]
     fn knockon_flash() {
-        let input = vec![
+        let input = [
             "820",
             "900",
             "000"
         ];
 
-        let mut grid = get_grid(&mut input.iter());
+        let mut grid = data::digit_grid(&input.join("\n")).expect("parsing failed!");
         assert_eq!(2, step(&mut grid));
 
         assert_eq!(0, grid.get(&Point::new(0, 1)));
@@ -133,27 +104,13 @@ mod test_step {
     }
 }
 
-fn get_grid(i: &mut Iter<&str>) -> Grid {
-    let mut grid: Vec<Vec<u32>> = Vec::new();
-    for s in i {
-        let mut v = Vec::new();
-        for c in s.chars() {
-            let n = format!("{}", c).parse().expect("Parse error!");
-            v.push(n);
-        }
-        grid.push(v);
-    }
-
-    Grid::from_array(grid)
-}
-
 fn step(grid: &mut Grid) -> u64 {
     // First increment each cell by 1.
     grid.do_each(&|v| v + 1);
 
     // Now calculate flashes.
     let mut flashes: HashSet<Point> = HashSet::new();
-    for p in grid.points() {
+    for p in grid.iter_points() {
         let v = grid.get(&p);
         if v > 9 {
             flashes.insert(p);
@@ -173,7 +130,7 @@ fn step(grid: &mut Grid) -> u64 {
             }
         }
 
-        for p in grid.points() {
+        for p in grid.iter_points() {
             if flashes.contains(&p) { continue; }
 
             let v = grid.get(&p);
@@ -216,20 +173,28 @@ fn first_synchronized_flash(grid: &mut Grid) -> u64 {
     }
 }
 
-fn get_data() -> Grid {
-    aoc::data::get_with_iter("data/day11.txt", &mut get_grid)
-}
+struct Day11;
 
-fn part1() -> u64 {
-    let mut grid = get_data();
-    total_flashes(&mut grid)
-}
+pub const DAY: Day = Day::new::<Day11>();
 
-fn part2() -> u64 {
-    let mut grid = get_data();
-    first_synchronized_flash(&mut grid)
-}
+impl Solution for Day11 {
+    type Input = Grid;
+    type Output1 = u64;
+    type Output2 = u64;
 
-fn main() {
-    aoc::solution!(11, "total flashes", "first sync flash");
+    const DAY: u32 = 11;
+    const PART1: &'static str = "total flashes";
+    const PART2: &'static str = "first sync flash";
+
+    fn parse(input: &str) -> Result<Grid, Error> {
+        Ok(data::digit_grid(input)?)
+    }
+
+    fn part1(grid: &Grid) -> Result<u64, Error> {
+        Ok(total_flashes(&mut grid.clone()))
+    }
+
+    fn part2(grid: &Grid) -> Result<u64, Error> {
+        Ok(first_synchronized_flash(&mut grid.clone()))
+    }
 }
@@ -1,21 +1,27 @@
 // Advent of Code
 // Day 12
 
+use std::error;
+use std::fmt;
 use std::str::FromStr;
+use std::time::Duration;
 
-
-use aoc::data;
+use aoc::{data, Context, Day, Error, Solution};
+use aoc::diagnostic::Diagnostic;
 
 #[cfg(test)]
 mod test_puzzles {
+    use super::*;
+    use aoc::answers;
+
     #[test]
     fn part1() {
-        assert_eq!(3450, super::part1());
+        assert_eq!(answers::expected(12, 1), Day12::part1(&aoc::input::<Day12>()).expect("part1 failed!").to_string());
     }
 
     #[test]
     fn part2() {
-        assert_eq!(96528, super::part2());
+        assert_eq!(answers::expected(12, 2), Day12::part2(&aoc::input::<Day12>()).expect("part2 failed!").to_string());
     }
 }
 
@@ -24,89 +30,14 @@ mod test_examples {
     use super::*;
 
     #[test]
-    fn part1_1() {
-        let input = vec![
-            "start-A",
-            "start-b",
-            "A-c",
-            "A-b",
-            "b-d",
-            "A-end",
-            "b-end",
-        ];
-
-        let input: Vec<CavePair> = input.iter().map(|i| CavePair::from_str(i).expect("Parse error!")).collect();
-        let caves = CaveGraph::new(&input);
-
-        let paths = find_paths(&caves, caves.start(), Vec::new());
-
-        assert_eq!(10, paths.expect("expected paths").len());
-    }
-
-    #[test]
-    fn part1_2() {
-        let input = vec![
-            "dc-end",
-            "HN-start",
-            "start-kj",
-            "dc-start",
-            "dc-HN",
-            "LN-dc",
-            "HN-end",
-            "kj-sa",
-            "kj-HN",
-            "kj-dc",
-        ];
-
-        let input: Vec<CavePair> = input.iter().map(|i| CavePair::from_str(i).expect("Parse error!")).collect();
-        let caves = CaveGraph::new(&input);
-
-        let paths = find_paths(&caves, caves.start(), Vec::new());
-
-        assert_eq!(19, paths.expect("expected paths").len());
-    }
-
-    #[test]
-    fn part2_1() {
-        let input = vec![
-            "start-A",
-            "start-b",
-            "A-c",
-            "A-b",
-            "b-d",
-            "A-end",
-            "b-end",
-        ];
-
-        let input: Vec<CavePair> = input.iter().map(|i| CavePair::from_str(i).expect("Parse error!")).collect();
-        let caves = CaveGraph::new(&input);
-
-        let paths = find_paths_visit_twice(&caves, caves.start(), Vec::new(), vec![0; caves.num_caves()]).expect("expected paths!");
-
-        assert_eq!(36, paths.len());
+    fn answers() {
+        aoc::check_examples::<Day12>();
     }
 
     #[test]
-    fn part2_2() {
-        let input = vec![
-            "dc-end",
-            "HN-start",
-            "start-kj",
-            "dc-start",
-            "dc-HN",
-            "LN-dc",
-            "HN-end",
-            "kj-sa",
-            "kj-HN",
-            "kj-dc",
-        ];
-
-        let input: Vec<CavePair> = input.iter().map(|i| CavePair::from_str(i).expect("Parse error!")).collect();
-        let caves = CaveGraph::new(&input);
-
-        let paths = find_paths_visit_twice(&caves, caves.start(), Vec::new(), vec![0; caves.num_caves()]);
-
-        assert_eq!(103, paths.expect("expected paths").len());
+    fn no_start() {
+        let caves = Day12::parse("A-b\nb-end\n").expect("parsing failed!");
+        assert_eq!("no start cave", Day12::part1(&caves).unwrap_err().to_string());
     }
 }
 
@@ -168,12 +99,22 @@ enum CaveParseError {
     Ambiguous
 }
 
+impl fmt::Display for CaveParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CaveParseError::Ambiguous => write!(f, "cave name is neither upper nor lower case")
+        }
+    }
+}
+
+impl error::Error for CaveParseError {}
+
 impl FromStr for Cave {
     type Err = CaveParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let name = String::from(s);
-        return if s == "start" {
+        if s == "start" {
             Ok(Cave { name, cavetype: CaveType::Start })
         }
         else if s == "end" {
@@ -196,31 +137,11 @@ struct CavePair {
     b: Cave
 }
 
-#[derive(Debug)]
-enum CavePairParseError {
-    StartInvalid(CaveParseError),
-    EndInvalid(CaveParseError),
-    Incomplete
-}
-
 impl FromStr for CavePair {
-    type Err = CavePairParseError;
+    type Err = Diagnostic;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut s = s.split('-');
-        let a = s.next().ok_or(CavePairParseError::Incomplete)?;
-        let b = s.next().ok_or(CavePairParseError::Incomplete)?;
-
-        let a = match Cave::from_str(a) {
-            Ok(c) => c,
-            Err(e) => return Err(CavePairParseError::StartInvalid(e))
-        };
-
-        let b = match Cave::from_str(b) {
-            Ok(c) => c,
-            Err(e) => return Err(CavePairParseError::EndInvalid(e))
-        };
-
+        let (a, b) = data::scan("{}-{}", s)?;
         Ok(CavePair { a, b })
     }
 }
@@ -231,7 +152,7 @@ struct CaveGraph {
 }
 
 impl CaveGraph {
-    fn new(pairs: &[CavePair]) -> CaveGraph {
+    fn new(pairs: &[CavePair]) -> Result<CaveGraph, Error> {
         // First get the set of caves.
         let mut caves = Vec::new();
         for pair in pairs {
@@ -243,19 +164,19 @@ impl CaveGraph {
         let mut edges = Vec::new();
         for pair in pairs {
             // Get index in caves of each end.
-            let a_index = caves.iter().position(|c| c.eq(&pair.a)).unwrap();
-            let b_index = caves.iter().position(|c| c.eq(&pair.b)).unwrap();
+            let a_index = caves.iter().position(|c| c.eq(&pair.a)).context(format!("unknown cave {}", pair.a.name))?;
+            let b_index = caves.iter().position(|c| c.eq(&pair.b)).context(format!("unknown cave {}", pair.b.name))?;
 
             // Add two edges.
             edges.push((a_index, b_index));
             edges.push((b_index, a_index));
         }
 
-        CaveGraph { caves, edges }
+        Ok(CaveGraph { caves, edges })
     }
 
-    fn start(&self) -> usize {
-        return self.caves.iter().position(|c| c.cavetype == CaveType::Start ).unwrap();
+    fn start(&self) -> Result<usize, Error> {
+        self.caves.iter().position(|c| c.cavetype == CaveType::Start).context("no start cave")
     }
 
     fn cave(&self, i: usize) -> &Cave {
@@ -334,22 +255,34 @@ fn find_paths_visit_twice(caves: &CaveGraph, c: usize, mut visited: Vec<usize>,
     Some(paths)
 }
 
-fn part1() -> u32 {
-    let pairs = data::get("data/day12.txt");
-    let caves = CaveGraph::new(&pairs);
+struct Day12;
 
-    let paths = find_paths(&caves, caves.start(), Vec::new()).expect("expected at least one path!");
-    paths.len() as u32
-}
+pub const DAY: Day = Day::new::<Day12>();
 
-fn part2() -> u32 {
-    let pairs = data::get("data/day12.txt");
-    let caves = CaveGraph::new(&pairs);
+impl Solution for Day12 {
+    type Input = CaveGraph;
+    type Output1 = u32;
+    type Output2 = u32;
 
-    let paths = find_paths_visit_twice(&caves, caves.start(), Vec::new(), vec![0; caves.num_caves()]).expect("expected at least one path!");
-    paths.len() as u32
-}
+    const DAY: u32 = 12;
+    const PART1: &'static str = "# paths";
+    const PART2: &'static str = "# paths (new rules)";
 
-fn main() {
-    aoc::solution!(12, "# paths", "# paths (new rules)");
+    // Finding every path is exponential in the number of small caves.
+    const TIMEOUT: Option<Duration> = Some(Duration::from_secs(60));
+
+    fn parse(input: &str) -> Result<CaveGraph, Error> {
+        let pairs: Vec<CavePair> = data::from_lines(input)?;
+        CaveGraph::new(&pairs)
+    }
+
+    fn part1(caves: &CaveGraph) -> Result<u32, Error> {
+        let paths = find_paths(caves, caves.start()?, Vec::new()).context("no path from start to end")?;
+        Ok(paths.len() as u32)
+    }
+
+    fn part2(caves: &CaveGraph) -> Result<u32, Error> {
+        let paths = find_paths_visit_twice(caves, caves.start()?, Vec::new(), vec![0; caves.num_caves()]).context("no path from start to end")?;
+        Ok(paths.len() as u32)
+    }
 }
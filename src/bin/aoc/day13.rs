@@ -0,0 +1,184 @@
+// Advent of Code 2021
+// Day 13
+
+use std::str::FromStr;
+
+use aoc::{data, Context, Day, Error, Solution};
+use aoc::data::FromCaptures;
+use aoc::diagnostic::Diagnostic;
+use aoc::drawing::*;
+
+#[cfg(test)]
+mod test_puzzles {
+    use super::*;
+    use aoc::answers;
+
+    #[test]
+    fn part1() {
+        assert_eq!(answers::expected(13, 1), Day13::part1(&aoc::input::<Day13>()).expect("part1 failed!").to_string());
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(answers::expected(13, 2), Day13::part2(&aoc::input::<Day13>()).expect("part2 failed!").to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_examples {
+    use super::*;
+    use aoc::data::Source;
+
+    #[test]
+    fn answers() {
+        aoc::check_examples::<Day13>();
+    }
+
+    // The dots on the paper, cropped to where they are.
+    fn paper(grid: &SparseGrid<bool>) -> String {
+        let options = RenderOptions { crop: true, ..RenderOptions::default() };
+        grid.render(|&dot| if dot { Some('#') } else { None }, &options)
+    }
+
+    #[test]
+    fn folded() {
+        let (points, folds) = aoc::example::<Day13>(Source::example(13, "ex1"));
+        let grid = fold_all(&points, &folds);
+        assert_eq!("#####\n#...#\n#...#\n#...#\n#####", paper(&grid));
+
+        // The first example's square isn't in the font.
+        assert_eq!(Err(OcrError::Height(5)), grid.letters(|&dot| dot));
+    }
+
+    #[test]
+    fn letters() {
+        let (points, folds) = aoc::example::<Day13>(Source::example(13, "ex2"));
+        let grid = fold_all(&points, &folds);
+        assert_eq!(".##...##...##.\n#..#.#..#.#..#\n#..#.#..#.#...\n####.#..#.#...\n#..#.#..#.#..#\n#..#..##...##.", paper(&grid));
+        assert_eq!(Ok(String::from("AOC")), grid.letters(|&dot| dot));
+    }
+}
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    #[test]
+    fn points_point_at_line() {
+        let e = Day13::parse("6,10\n0,x4\n\nfold along y=7\n").err().expect("parsing succeeded!");
+        assert_eq!("section 1: y coordinate is not a number: invalid digit found in string\n --> line 2, column 3\n  |\n2 | 0,x4\n  |   ^^", e.to_string());
+    }
+
+    #[test]
+    fn bad_axis() {
+        let e = Fold::from_str("fold along z=7").err().expect("parsing succeeded!");
+        assert_eq!((12..13, "'z': expected x or y"), (e.span, e.message.as_str()));
+
+        let e = Fold::from_str("fold along  xy=7").err().expect("parsing succeeded!");
+        assert_eq!((12..15, "' xy': expected x or y"), (e.span, e.message.as_str()));
+    }
+}
+
+enum Fold {
+    Vertical(i64),
+    Horizontal(i64)
+}
+
+impl FromStr for Fold {
+    type Err = Diagnostic;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let captures = data::scan_captures("fold along {}={}", s)?;
+        let (axis, value): (String, i64) = FromCaptures::from_captures(s, &captures)?;
+
+        match axis.as_str() {
+            "x" => Ok(Fold::Vertical(value)),
+            "y" => Ok(Fold::Horizontal(value)),
+            _ => {
+                let column = captures[0].0;
+                Err(Diagnostic::new(s, column..column + axis.chars().count(), format!("'{}': expected x or y", axis)))
+            }
+        }
+    }
+}
+
+fn fold_along_x(grid: SparseGrid<bool>, y: i64) -> SparseGrid<bool> {
+    let points = grid.points();
+
+    let mut new_points = Vec::new();
+    for p in points {
+        if p.y < y { new_points.push(p); }
+        else if p.y > y {
+            // Calculate new y position of the point.
+            let dist = (p.y - y).abs();
+            let new_y = y - dist;
+            new_points.push(Point::new(p.x, new_y));
+        }
+    }
+
+    SparseGrid::from_points(&new_points, true)
+}
+
+fn fold_along_y(grid: SparseGrid<bool>, x: i64) -> SparseGrid<bool> {
+    let points = grid.points();
+
+    let mut new_points = Vec::new();
+    for p in points {
+        if p.x < x { new_points.push(p); }
+        else if p.x > x {
+            // Calculate new y position of the point.
+            let dist = (p.x - x).abs();
+            let new_x = x - dist;
+            new_points.push(Point::new(new_x, p.y));
+        }
+    }
+
+    SparseGrid::from_points(&new_points, true)
+}
+
+fn fold(grid: SparseGrid<bool>, fold: &Fold) -> SparseGrid<bool> {
+    match *fold {
+        Fold::Horizontal(y) => fold_along_x(grid, y),
+        Fold::Vertical(x) => fold_along_y(grid, x)
+    }
+}
+
+fn fold_all(points: &[Point], folds: &[Fold]) -> SparseGrid<bool> {
+    let mut grid = SparseGrid::from_points(points, true);
+
+    for f in folds {
+        grid = fold(grid, f);
+    }
+
+    grid
+}
+
+struct Day13;
+
+pub const DAY: Day = Day::new::<Day13>();
+
+impl Solution for Day13 {
+    type Input = (Vec<Point>, Vec<Fold>);
+    type Output1 = u32;
+    type Output2 = String;
+
+    const DAY: u32 = 13;
+    const PART1: &'static str = "after first fold";
+    const PART2: &'static str = "code";
+
+    // The points, then a blank line, then the folds.
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        data::sections(input)
+    }
+
+    fn part1((points, folds): &Self::Input) -> Result<u32, Error> {
+        let first = folds.first().context("no folds")?;
+        let grid = fold(SparseGrid::from_points(points, true), first);
+        Ok(grid.count(&|dot| dot))
+    }
+
+    fn part2((points, folds): &Self::Input) -> Result<String, Error> {
+        let grid = fold_all(points, folds);
+        Ok(grid.letters(|&dot| dot)?)
+    }
+}
@@ -0,0 +1,228 @@
+// Advent of Code 2021
+// Day 14
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use aoc::{data, Context, Day, Error, Solution};
+use aoc::diagnostic::Diagnostic;
+
+#[cfg(test)]
+mod test_puzzles {
+    use super::*;
+    use aoc::answers;
+
+    #[test]
+    fn part1() {
+        assert_eq!(answers::expected(14, 1), Day14::part1(&aoc::input::<Day14>()).expect("part1 failed!").to_string());
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(answers::expected(14, 2), Day14::part2(&aoc::input::<Day14>()).expect("part2 failed!").to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_examples {
+    use super::*;
+    use aoc::data::Source;
+
+    #[test]
+    fn answers() {
+        aoc::check_examples::<Day14>();
+    }
+
+    #[test]
+    fn part1() {
+        let input = aoc::example::<Day14>(Source::example(14, "ex1"));
+        let (template, rules) = &input;
+
+        let (pairs, counts) = pair_insertion(to_pairs(template), to_counts(template), rules).expect("insertion failed!");
+        assert_eq!(1, pairs["NC"]);
+        assert_eq!(1, pairs["NB"]);
+        assert_eq!(1, pairs["CN"]);
+        assert_eq!(1, pairs["BC"]);
+        assert_eq!(1, pairs["CH"]);
+        assert_eq!(1, pairs["HB"]);
+
+        let (pairs, _) = pair_insertion(pairs, counts, rules).expect("insertion failed!");
+        assert_eq!(2, pairs["NB"]);
+        assert_eq!(2, pairs["BC"]);
+        assert_eq!(1, pairs["CC"]);
+        assert_eq!(1, pairs["CN"]);
+        assert_eq!(2, pairs["BB"]);
+        assert_eq!(2, pairs["CB"]);
+        assert_eq!(1, pairs["BH"]);
+        assert_eq!(1, pairs["HC"]);
+    }
+
+    #[test]
+    fn bad_rule() {
+        let e = get_data("NNCB\n\nCH -> B\nHH\n").unwrap_err();
+        assert_eq!("section 2: expected ' -> '\n --> line 4, column 3\n  |\n4 | HH\n  |   ^", e.to_string());
+
+        let e = get_data("NNCB\n\nCHH -> B\n").unwrap_err();
+        assert!(e.to_string().ends_with("3 | CHH -> B\n  | ^^^"));
+    }
+
+    #[test]
+    fn missing_rule() {
+        let (template, rules) = get_data("NNC\n\nNN -> C\n").expect("parsing failed!");
+        assert_eq!("no insertion rule for NC", result_after_n(&template, &rules, 1).unwrap_err().to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_insertion {
+    use super::*;
+
+    #[test]
+    fn insert_one() {
+        let mut rules = HashMap::new();
+        rules.insert(String::from("AB"), 'C');
+
+        let (pairs, counts) = pair_insertion(to_pairs("AB"), to_counts("AB"), &rules).expect("insertion failed!");
+        assert_eq!(1, pairs["AC"]);
+        assert_eq!(1, pairs["CB"]);
+
+        assert_eq!(1, counts[&'A']);
+        assert_eq!(1, counts[&'C']);
+        assert_eq!(1, counts[&'B']);
+    }
+
+    #[test]
+    fn insert_two() {
+        let mut rules = HashMap::new();
+        rules.insert(String::from("AB"), 'C');
+        rules.insert(String::from("BA"), 'D'    );
+
+        let (pairs, counts) = pair_insertion(to_pairs("ABA"), to_counts("ABA"), &rules).expect("insertion failed!");
+        assert_eq!(1, pairs["AC"]);
+        assert_eq!(1, pairs["CB"]);
+        assert_eq!(1, pairs["BD"]);
+        assert_eq!(1, pairs["DA"]);
+
+        assert_eq!(1, counts[&'C']);
+        assert_eq!(2, counts[&'A']);
+        assert_eq!(1, counts[&'B']);
+        assert_eq!(1, counts[&'D']);
+    }
+}
+
+fn to_pairs(input: &str) -> HashMap<String, u64> {
+    let mut pairs = HashMap::new();
+
+    let chars: Vec<char> = input.chars().collect();
+    for pair in chars.windows(2) {
+        *pairs.entry(pair.iter().collect()).or_insert(0) += 1;
+    }
+
+    pairs
+}
+
+fn to_counts(input: &str) -> HashMap<char, u64> {
+    let mut counts = HashMap::new();
+
+    // Set initial counts of characters.
+    for c in input.chars() {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+// Counts of each pair, and of each element.
+type Polymer = (HashMap<String, u64>, HashMap<char, u64>);
+
+// Given a string and a set of insertion rules, return the string resulting
+// from applying those rules to the input.
+fn pair_insertion(pairs: HashMap<String, u64>, mut counts: HashMap<char, u64>, rules: &HashMap<String, char>) -> Result<Polymer, Error> {
+    let mut new_pairs = HashMap::new();
+
+    for (pair, count) in pairs {
+        let mut chars = pair.chars();
+        let (left, right) = chars.next().zip(chars.next()).context(format!("'{}' is not a pair", pair))?;
+
+        let insert = *rules.get(&pair).context(format!("no insertion rule for {}", pair))?;
+        *counts.entry(insert).or_insert(0) += count;
+
+        *new_pairs.entry(format!("{}{}", left, insert)).or_insert(0) += count;
+        *new_pairs.entry(format!("{}{}", insert, right)).or_insert(0) += count;
+    }
+
+    Ok((new_pairs, counts))
+}
+
+// A pair insertion rule, e.g. "CH -> B".
+struct Rule {
+    pair: String,
+    insert: char
+}
+
+impl FromStr for Rule {
+    type Err = Diagnostic;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (pair, insert): (String, char) = data::scan("{} -> {}", s)?;
+        if pair.chars().count() != 2 {
+            return Err(Diagnostic::new(s, 1..pair.chars().count() + 1, "expected a pair of elements"));
+        }
+
+        Ok(Rule { pair, insert })
+    }
+}
+
+fn get_data(input: &str) -> Result<(String, HashMap<String, char>), Error> {
+    let (template, rules) = data::sections::<(String, Vec<Rule>)>(input)?;
+    if template.len() < 2 {
+        return Err(Error::msg("polymer template needs at least two elements"));
+    }
+
+    let rules = rules.into_iter().map(|r| (r.pair, r.insert)).collect();
+    Ok((template, rules))
+}
+
+fn result_after_n(input: &str, rules: &HashMap<String, char>, n: i64) -> Result<u64, Error> {
+    let mut pairs = to_pairs(input);
+    let mut counts = to_counts(input);
+
+    for _ in 0..n {
+        let result = pair_insertion(pairs, counts, rules)?;
+        pairs = result.0;
+        counts = result.1;
+    }
+
+    // Find the most common and the least common.
+    let most = counts.values().max().context("polymer has no elements")?;
+    let least = counts.values().min().context("polymer has no elements")?;
+
+    Ok(most - least)
+}
+
+struct Day14;
+
+pub const DAY: Day = Day::new::<Day14>();
+
+impl Solution for Day14 {
+    type Input = (String, HashMap<String, char>);
+    type Output1 = u64;
+    type Output2 = u64;
+
+    const DAY: u32 = 14;
+    const PART1: &'static str = "#most - #least (n=10)";
+    const PART2: &'static str = "#most - #least (n=40)";
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        get_data(input)
+    }
+
+    fn part1((template, rules): &Self::Input) -> Result<u64, Error> {
+        result_after_n(template, rules, 10)
+    }
+
+    fn part2((template, rules): &Self::Input) -> Result<u64, Error> {
+        result_after_n(template, rules, 40)
+    }
+}
+
@@ -1,45 +1,42 @@
 // Advent of Code 2021
 // Day 15
 
-use std::{slice::Iter, collections::HashMap};
-use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use aoc::{data, Context, Day, Error, Solution};
 use aoc::drawing::{Grid, Point};
 
 #[cfg(test)]
 mod test_puzzles {
+    use super::*;
+    use aoc::answers;
+
     #[test]
     fn part1() {
-        assert_eq!(652, super::part1());
+        assert_eq!(answers::expected(15, 1), Day15::part1(&aoc::input::<Day15>()).expect("part1 failed!").to_string());
     }
 
     #[test]
     fn part2() {
-        assert_eq!(2938, super::part2());
+        assert_eq!(answers::expected(15, 2), Day15::part2(&aoc::input::<Day15>()).expect("part2 failed!").to_string());
     }
 }
 
 #[cfg(test)]
 mod test_examples {
     use super::*;
+    use aoc::data::Source;
 
     #[test]
-    fn part1() {
-        let input = vec![
-            "1163751742",
-            "1381373672",
-            "2136511328",
-            "3694931569",
-            "7463417111",
-            "1319128137",
-            "1359912421",
-            "3125421639",
-            "1293138521",
-            "2311944581",
-        ];
+    fn answers() {
+        aoc::check_examples::<Day15>();
+    }
 
-        let grid = get_grid(&mut input.iter());
+    #[test]
+    fn part1() {
+        let grid = aoc::example::<Day15>(Source::example(15, "ex1"));
 
-        let shortest_path = a_star(&grid, Point::new(0, 0), Point::new(9, 9));
+        let shortest_path = a_star(&grid, Point::new(0, 0), Point::new(9, 9)).expect("path not found!");
 
         let expected = vec![
             (0, 0),
@@ -67,91 +64,16 @@ mod test_examples {
 
         assert_eq!(expected, shortest_path);
     }
-
-    #[test]
-    fn part2() {
-        let input = vec![
-            "1163751742",
-            "1381373672",
-            "2136511328",
-            "3694931569",
-            "7463417111",
-            "1319128137",
-            "1359912421",
-            "3125421639",
-            "1293138521",
-            "2311944581",
-        ];
-
-        let mut grid = get_grid(&mut input.iter());
-
-        let mut other = grid.clone();
-        
-        for _ in 0..4 {
-            other.do_each(&|v| if v + 1 > 9 { 1 } else { v + 1 });
-            grid_append_right(&mut grid, &other);
-        }
-
-        let mut other = grid.clone();
-
-        for _ in 0..4 {
-            other.do_each(&|v| if v + 1 > 9 { 1 } else { v + 1 });
-            grid_append_down(&mut grid, &other);
-        }
-
-        for y in 0..grid.ysize() {
-            for x in 0..grid.xsize() {
-                let v = grid.get(&Point::new(x as i64, y as i64));
-                print!("{}", v);
-            }
-            println!();
-        }
-
-        let shortest_path = a_star(&grid, Point::new(0, 0), Point::new(grid.xsize() - 1, grid.ysize() - 1));
-
-        assert_eq!(315, risk(&grid, &shortest_path));
-    }
 }
 
 fn grid_append_right(grid: &mut Grid, other: &Grid) {
-    let new_origin = Point::new(grid.xsize() as i64, 0);
-    let other = other.move_to(&new_origin);
-
-    for point in other.points() {
-        let v = other.get(&point);
-        grid.set(&point, v);
-    }
+    let new_origin = Point::new(grid.xsize(), 0);
+    grid.paste(other, &new_origin);
 }
 
 fn grid_append_down(grid: &mut Grid, other: &Grid) {
     let new_origin = Point::new(0, grid.ysize());
-    let other = other.move_to(&new_origin);
-
-    for point in other.points() {
-        let v = other.get(&point);
-        grid.set(&point, v);
-    }
-}
-
-fn get_grid(i: &mut Iter<&str>) -> Grid {
-    let mut grid: Vec<Vec<u32>> = Vec::new();
-    for s in i {
-        let mut v = Vec::new();
-        for c in s.chars() {
-            let n = format!("{}", c).parse().expect("Parse error!");
-            v.push(n);
-        }
-        grid.push(v);
-    }
-
-    Grid::from_array(grid)
-}
-
-fn get_f_score(p: &Point, f_score: &HashMap<Point, i64>) -> i64 {
-    match f_score.get(p) {
-        Some(v) => *v,
-        None => i64::MAX
-    }
+    grid.paste(other, &new_origin);
 }
 
 fn get_g_score(p: &Point, g_score: &HashMap<Point, i64>) -> i64 {
@@ -175,7 +97,7 @@ fn path(p: Point, came_from: &HashMap<Point, Point>) -> Vec<Point> {
 
 // Implementation of the A* search algorithm.
 // Implemented with help from: https://en.wikipedia.org/wiki/A*_search_algorithm#Pseudocode
-fn a_star(grid: &Grid, start: Point, end: Point) -> Vec<Point> {
+fn a_star(grid: &Grid, start: Point, end: Point) -> Option<Vec<Point>> {
     // Set of nodes under consideration for search.
     let mut open_set: Vec<Point> = vec![start];
 
@@ -194,15 +116,15 @@ fn a_star(grid: &Grid, start: Point, end: Point) -> Vec<Point> {
     let mut f_score: HashMap<Point, i64> = HashMap::new();
     f_score.insert(start, start.manhattan(&end));
 
-    while !open_set.is_empty() {
+    while let Some(current) = open_set.pop() {
         // Lowest f_score is at the end of the set.
-        let current = open_set.pop().unwrap();
+        
 
         if current == end {
-            return path(current, &came_from);
+            return Some(path(current, &came_from));
         }
 
-        for n in grid.neighbours(&current) {
+        for n in grid.iter_neighbours(&current) {
             // Weight of the edge is the risk level of entering the neighbour.
             let d = grid.get(&n) as i64;
 
@@ -228,7 +150,7 @@ fn a_star(grid: &Grid, start: Point, end: Point) -> Vec<Point> {
                         open_set.push(n);
                     }
                     else {
-                        let i = open_set.iter().position(|p| f_score[&p] < score).unwrap();
+                        let i = open_set.iter().position(|p| f_score[p] < score).unwrap();
                         open_set.insert(i, n);
                     }
                 }
@@ -236,7 +158,7 @@ fn a_star(grid: &Grid, start: Point, end: Point) -> Vec<Point> {
         }
     }
 
-    panic!("Path not found!");
+    None
 }
 
 fn risk(grid: &Grid, path: &[Point]) -> u64 {
@@ -250,41 +172,53 @@ fn risk(grid: &Grid, path: &[Point]) -> u64 {
     risk as u64
 }
 
-fn part1() -> u64 {
-    let grid = aoc::data::get_with_iter("data/day15.txt", &mut get_grid);
-    let start = Point::new(0, 0);
-    let end = Point::new((grid.xsize() - 1) as i64, (grid.ysize() - 1) as i64);
+struct Day15;
 
-    let shortest_path = a_star(&grid, start, end);
+pub const DAY: Day = Day::new::<Day15>();
 
-    risk(&grid, &shortest_path)
-}
+impl Solution for Day15 {
+    type Input = Grid;
+    type Output1 = u64;
+    type Output2 = u64;
 
-fn part2() -> u64 {
-    let mut grid = aoc::data::get_with_iter("data/day15.txt", &mut get_grid);
+    const DAY: u32 = 15;
+    const PART1: &'static str = "lowest risk";
+    const PART2: &'static str = "lowest risk (big grid)";
 
-    let mut other = grid.clone();
-        
-    for _ in 0..4 {
-        other.do_each(&|v| if v + 1 > 9 { 1 } else { v + 1 });
-        grid_append_right(&mut grid, &other);
+    fn parse(input: &str) -> Result<Grid, Error> {
+        Ok(data::digit_grid(input)?)
     }
 
-    let mut other = grid.clone();
+    fn part1(grid: &Grid) -> Result<u64, Error> {
+        let start = Point::new(0, 0);
+        let end = Point::new(grid.xsize() - 1, grid.ysize() - 1);
 
-    for _ in 0..4 {
-        other.do_each(&|v| if v + 1 > 9 { 1 } else { v + 1 });
-        grid_append_down(&mut grid, &other);
+        let shortest_path = a_star(grid, start, end).context("no path from start to end")?;
+
+        Ok(risk(grid, &shortest_path))
     }
 
-    let start = Point::new(0, 0);
-    let end = Point::new((grid.xsize() - 1) as i64, (grid.ysize() - 1) as i64);
+    fn part2(grid: &Grid) -> Result<u64, Error> {
+        let mut grid = grid.clone();
+        let mut other = grid.clone();
 
-    let shortest_path = a_star(&grid, start, end);
+        for _ in 0..4 {
+            other.do_each(&|v| if v + 1 > 9 { 1 } else { v + 1 });
+            grid_append_right(&mut grid, &other);
+        }
 
-    risk(&grid, &shortest_path)
-}
+        let mut other = grid.clone();
 
-fn main() {
-    aoc::solution!(15, "lowest risk", "lowest risk (big grid)");
+        for _ in 0..4 {
+            other.do_each(&|v| if v + 1 > 9 { 1 } else { v + 1 });
+            grid_append_down(&mut grid, &other);
+        }
+
+        let start = Point::new(0, 0);
+        let end = Point::new(grid.xsize() - 1, grid.ysize() - 1);
+
+        let shortest_path = a_star(&grid, start, end).context("no path from start to end")?;
+
+        Ok(risk(&grid, &shortest_path))
+    }
 }
\ No newline at end of file
@@ -1,135 +1,65 @@
 // Advent of Code 2021
 // Day 16
 
+use std::error;
+use std::fmt;
 use std::str::{FromStr, Chars};
 
-#[cfg(test)]
-mod test_puzzles {
-    #[test]
-    fn part1() {
-        assert_eq!(984, super::part1());
-    }
-}
+use aoc::{Day, Error, Solution};
 
 #[cfg(test)]
-mod test_examples {
+mod test_puzzles {
     use super::*;
+    use aoc::answers;
 
     #[test]
-    fn literal() {
-        let input = "D2FE28";
-
-        let packet = Packet::from_str(input).expect("parsing failed!");
-        assert_eq!(Packet::Literal(6, 2021), packet);
-    }
-
-    #[test]
-    fn op_1() {
-        let input = "38006F45291200";
-
-        let packet = Packet::from_str(input).expect("parsing failed!");
-        assert_eq!(Packet::Operator(1, Operation::LessThan, vec![Packet::Literal(6, 10), Packet::Literal(2, 20)]), packet);
-    }
-
-    #[test]
-    fn version_sum_1() {
-        let input = "8A004A801A8002F478";
-
-        let packet = Packet::from_str(input).expect("parsing failed!");
-        assert_eq!(16, version_sum(&packet));
-    }
-
-    #[test]
-    fn version_sum_2() {
-        let input = "620080001611562C8802118E34";
-
-        let packet = Packet::from_str(input).expect("parsing failed!");
-        assert_eq!(12, version_sum(&packet));
+    fn part1() {
+        assert_eq!(answers::expected(16, 1), Day16::part1(&aoc::input::<Day16>()).expect("part1 failed!").to_string());
     }
 
     #[test]
-    fn version_sum_3() {
-        let input = "C0015000016115A2E0802F182340";
-
-        let packet = Packet::from_str(input).expect("parsing failed!");
-        assert_eq!(23, version_sum(&packet));
-    }
-
-    #[test]
-    fn version_sum_4() {
-        let input = "A0016C880162017C3686B18A3D4780";
-
-        let packet = Packet::from_str(input).expect("parsing failed!");
-        assert_eq!(31, version_sum(&packet));
+    fn part2() {
+        assert_eq!(answers::expected(16, 2), Day16::part2(&aoc::input::<Day16>()).expect("part2 failed!").to_string());
     }
 }
 
 #[cfg(test)]
-mod test_examples_operators {
+mod test_examples {
     use super::*;
 
     #[test]
-    fn value_1() {
-        let input = "C200B40A82";
-
-        let packet = Packet::from_str(input).expect("parsing failed!");
-        assert_eq!(3, value(&packet));
-    }
-
-    #[test]
-    fn value_2() {
-        let input = "04005AC33890";
-
-        let packet = Packet::from_str(input).expect("parsing failed!");
-        assert_eq!(54, value(&packet));
-    }
-
-    #[test]
-    fn value_3() {
-        let input = "880086C3E88112";
-
-        let packet = Packet::from_str(input).expect("parsing failed!");
-        assert_eq!(7, value(&packet));
+    fn answers() {
+        aoc::check_examples::<Day16>();
     }
 
     #[test]
-    fn value_4() {
-        let input = "CE00C43D881120";
+    fn literal() {
+        let input = "D2FE28";
 
         let packet = Packet::from_str(input).expect("parsing failed!");
-        assert_eq!(9, value(&packet));
+        assert_eq!(Packet::Literal(6, 2021), packet);
     }
 
     #[test]
-    fn value_5() {
-        let input = "D8005AC2A8F0";
+    fn op_1() {
+        let input = "38006F45291200";
 
         let packet = Packet::from_str(input).expect("parsing failed!");
-        assert_eq!(1, value(&packet));
+        assert_eq!(Packet::Operator(1, Operation::LessThan, vec![Packet::Literal(6, 10), Packet::Literal(2, 20)]), packet);
     }
 
     #[test]
-    fn value_6() {
-        let input = "F600BC2D8F";
-
-        let packet = Packet::from_str(input).expect("parsing failed!");
-        assert_eq!(0, value(&packet));
+    fn comparison_needs_two() {
+        // A less than packet with only one sub-packet, the literal 10.
+        let packet = Packet::from_str("3A007450").expect("parsing failed!");
+        assert_eq!(Packet::Operator(1, Operation::LessThan, vec![Packet::Literal(6, 10)]), packet);
+        assert_eq!("LessThan has 1 sub-packets, expected 2", value(&packet).unwrap_err().to_string());
     }
 
     #[test]
-    fn value_7() {
-        let input = "9C005AC2F8F0";
-
-        let packet = Packet::from_str(input).expect("parsing failed!");
-        assert_eq!(0, value(&packet));
-    }
-
-    #[test]
-    fn value_8() {
-        let input = "9C0141080250320F1802104A08";
-
-        let packet = Packet::from_str(input).expect("parsing failed!");
-        assert_eq!(1, value(&packet));
+    fn not_hex() {
+        let result = Packet::from_str("D2FG28");
+        assert!(matches!(result, Err(PacketParseError::Hex('G'))));
     }
 }
 
@@ -152,16 +82,32 @@ enum Packet {
 
 #[derive(Debug)]
 enum PacketParseError {
-    InvalidLiteral,
-    InvalidVersion,
-    InvalidLengthId,
-    InvalidLengthField,
-    InvalidOperation
+    Hex(char),
+    Literal,
+    Version,
+    LengthId,
+    LengthField,
+    Operation
+}
+
+impl fmt::Display for PacketParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PacketParseError::Hex(c) => write!(f, "'{}' is not a hexadecimal digit", c),
+            PacketParseError::Literal => write!(f, "truncated literal value"),
+            PacketParseError::Version => write!(f, "truncated packet header"),
+            PacketParseError::LengthId => write!(f, "missing length type ID"),
+            PacketParseError::LengthField => write!(f, "truncated sub-packet length"),
+            PacketParseError::Operation => write!(f, "unknown operation")
+        }
+    }
 }
 
-fn to_hex(s: &str) -> String {
-    let b = u32::from_str_radix(s, 16).expect("not hexadecimal!");
-    format!("{:04b}", b)
+impl error::Error for PacketParseError {}
+
+fn to_hex(c: char) -> Result<String, PacketParseError> {
+    let b = c.to_digit(16).ok_or(PacketParseError::Hex(c))?;
+    Ok(format!("{:04b}", b))
 }
 
 impl FromStr for Packet {
@@ -170,7 +116,7 @@ impl FromStr for Packet {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut packet = String::new();
         for c in s.chars() {
-            packet.push_str(&to_hex(&String::from(c)));
+            packet.push_str(&to_hex(c)?);
         }
 
         let mut packet_iter = packet.chars();
@@ -185,11 +131,11 @@ impl Packet {
         let mut bits = 0;
 
         // Get the version - 3 bits.
-        let version = get_bits(b, 3).ok_or(PacketParseError::InvalidVersion)?;
+        let version = get_bits(b, 3).ok_or(PacketParseError::Version)?;
         bits += 3;
 
         // Get ID - 3 bits.
-        let id = get_bits(b, 3).ok_or(PacketParseError::InvalidVersion)?;
+        let id = get_bits(b, 3).ok_or(PacketParseError::Version)?;
         bits += 3;
 
         let packet = if id == 4 {
@@ -197,7 +143,7 @@ impl Packet {
             let mut literal_value = 0;
             loop {
                 literal_value <<= 4;
-                let literal = get_bits(b, 5).ok_or(PacketParseError::InvalidLiteral)?;
+                let literal = get_bits(b, 5).ok_or(PacketParseError::Literal)?;
                 bits += 5;
 
                 literal_value |= literal & 0b01111;
@@ -211,12 +157,12 @@ impl Packet {
             Self::Literal(version, literal_value)
         }
         else {
-            let length_id = get_bits(b, 1).ok_or(PacketParseError::InvalidLengthId)?;
+            let length_id = get_bits(b, 1).ok_or(PacketParseError::LengthId)?;
             bits += 1;
 
             let length_field = if length_id == 1 { 11 } else { 15 };
             
-            let sub_packets_length = get_bits(b, length_field).ok_or(PacketParseError::InvalidLengthField)?;
+            let sub_packets_length = get_bits(b, length_field).ok_or(PacketParseError::LengthField)?;
             bits += length_field;
 
             let mut sub_packets = Vec::new();
@@ -247,7 +193,7 @@ impl Packet {
                 5 => Operation::GreaterThan,
                 6 => Operation::LessThan,
                 7 => Operation::EqualTo,
-                _ => return Err(PacketParseError::InvalidOperation)
+                _ => return Err(PacketParseError::Operation)
             };
 
             Self::Operator(version, op, sub_packets)
@@ -279,7 +225,7 @@ fn version_sum(p: &Packet) -> u64 {
             let mut sum = *v;
 
             for sp in sub_packets {
-                sum += version_sum(&sp);
+                sum += version_sum(sp);
             }
 
             sum
@@ -287,85 +233,82 @@ fn version_sum(p: &Packet) -> u64 {
     }
 }
 
-fn value(p: &Packet) -> u64 {
+fn value(p: &Packet) -> Result<u64, Error> {
     match p {
-        Packet::Literal(_, val) => *val,
+        Packet::Literal(_, val) => Ok(*val),
         Packet::Operator(_, op, subpackets) => value_operator(op, subpackets)
     }
 }
 
-fn value_operator(op: &Operation, packets: &Vec<Packet>) -> u64 {
+fn value_operator(op: &Operation, packets: &[Packet]) -> Result<u64, Error> {
+    let overflow = || Error::msg(format!("{:?} of sub-packets overflows", op));
+
     match *op {
         Operation::Sum => {
-            packets.iter().map(|p| value(p)).sum()
+            let mut sum: u64 = 0;
+            for p in packets {
+                sum = sum.checked_add(value(p)?).ok_or_else(overflow)?;
+            }
+            Ok(sum)
         },
         Operation::Product => {
-            let mut product = 1;
-            for v in packets.iter().map(|p| value(p)) {
-                product *= v;
+            let mut product: u64 = 1;
+            for p in packets {
+                product = product.checked_mul(value(p)?).ok_or_else(overflow)?;
             }
-            product
-        }
+            Ok(product)
+        },
         Operation::Minimum => {
-            match packets.iter().map(|p| value(p)).min() {
-                Some(v) => v,
-                None => 0
-            }
+            Ok(packets.iter().map(value).collect::<Result<Vec<_>, _>>()?.into_iter().min().unwrap_or(0))
         },
         Operation::Maximum => {
-            match packets.iter().map(|p| value(p)).max() {
-                Some(v) => v,
-                None => 0
-            }
+            Ok(packets.iter().map(value).collect::<Result<Vec<_>, _>>()?.into_iter().max().unwrap_or(0))
         },
         Operation::GreaterThan => {
-            let lhs = &packets[0];
-            let rhs = &packets[1];
-
-            if value(lhs) > value(rhs) {
-                1
-            }
-            else {
-                0
-            }
+            let (lhs, rhs) = operands(op, packets)?;
+            Ok(if lhs > rhs { 1 } else { 0 })
         },
         Operation::LessThan => {
-            let lhs = &packets[0];
-            let rhs = &packets[1];
-
-            if value(lhs) < value(rhs) {
-                1
-            }
-            else {
-                0
-            }
+            let (lhs, rhs) = operands(op, packets)?;
+            Ok(if lhs < rhs { 1 } else { 0 })
         },
         Operation::EqualTo => {
-            let lhs = &packets[0];
-            let rhs = &packets[1];
-
-            if value(lhs) == value(rhs) {
-                1
-            }
-            else {
-                0
-            }
+            let (lhs, rhs) = operands(op, packets)?;
+            Ok(if lhs == rhs { 1 } else { 0 })
         }
     }
 }
 
-fn part1() -> u64 {
-    let packet: String = aoc::data::get::<String>("data/day16.txt")[0].clone();
-    let packet = Packet::from_str(&packet).expect("parsing packet failed!");
-    return version_sum(&packet);
+// The values of the two sub-packets a comparison needs.
+fn operands(op: &Operation, packets: &[Packet]) -> Result<(u64, u64), Error> {
+    match packets {
+        [lhs, rhs] => Ok((value(lhs)?, value(rhs)?)),
+        _ => Err(Error::msg(format!("{:?} has {} sub-packets, expected 2", op, packets.len())))
+    }
 }
 
-fn part2() -> u64 {
-    let packet: String = aoc::data::get::<String>("data/day16.txt")[0].clone();
-    let packet = Packet::from_str(&packet).expect("parsing packet failed!");
-    return value(&packet);
-}
+struct Day16;
+
+pub const DAY: Day = Day::new::<Day16>();
+
+impl Solution for Day16 {
+    type Input = Packet;
+    type Output1 = u64;
+    type Output2 = u64;
 
-fn main() {
-    aoc::solution!(16, "version sum", "value");
+    const DAY: u32 = 16;
+    const PART1: &'static str = "version sum";
+    const PART2: &'static str = "value";
+
+    fn parse(input: &str) -> Result<Packet, Error> {
+        Ok(Packet::from_str(input.trim())?)
+    }
+
+    fn part1(packet: &Packet) -> Result<u64, Error> {
+        Ok(version_sum(packet))
+    }
+
+    fn part2(packet: &Packet) -> Result<u64, Error> {
+        value(packet)
+    }
 }
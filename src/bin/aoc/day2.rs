@@ -0,0 +1,302 @@
+// Advent of Code 2021
+// Day 2
+
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+use aoc::{data, Context, Day, Error, Solution};
+use aoc::diagnostic::Diagnostic;
+
+#[cfg(test)]
+mod test_puzzles {
+    use super::*;
+    use aoc::answers;
+
+    #[test]
+    fn part1() {
+        assert_eq!(answers::expected(2, 1), Day2::part1(&aoc::input::<Day2>()).expect("part1 failed!").to_string());
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(answers::expected(2, 2), Day2::part2(&aoc::input::<Day2>()).expect("part2 failed!").to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_examples {
+    use super::*;
+    use aoc::data::Source;
+
+    #[test]
+    fn answers() {
+        aoc::check_examples::<Day2>();
+    }
+
+    #[test]
+    fn part1() {
+        let commands = aoc::example::<Day2>(Source::example(2, "ex1"));
+
+        let position = final_position(&commands).expect("moving failed!");
+        assert_eq!(10, position.depth);
+        assert_eq!(15, position.horizontal);
+    }
+
+    #[test]
+    fn part2() {
+        let commands = aoc::example::<Day2>(Source::example(2, "ex1"));
+
+        let position = final_position_with_aim(&commands).expect("moving failed!");
+        assert_eq!(60, position.depth);
+        assert_eq!(15, position.horizontal);
+    }
+}
+
+#[cfg(test)]
+mod test_do_commands {
+    use super::*;
+
+    #[test]
+    fn forward() {
+        let commands = vec![
+            Command { dir: Direction::Forward, val: 5 }
+        ];
+
+        let position = final_position(&commands).expect("moving failed!");
+
+        assert_eq!(0, position.depth);
+        assert_eq!(5, position.horizontal);
+    }
+
+    #[test]
+    fn down() {
+        let commands = vec![
+            Command { dir: Direction::Down, val: 19 }
+        ];
+
+        let position = final_position(&commands).expect("moving failed!");
+
+        assert_eq!(19, position.depth);
+        assert_eq!(0, position.horizontal);
+    }
+
+    #[test]
+    fn down_up() {
+        let commands = vec![
+            Command { dir: Direction::Down, val: 19 },
+            Command { dir: Direction::Up, val: 4 }
+        ];
+
+        let position = final_position(&commands).expect("moving failed!");
+
+        assert_eq!(15, position.depth);
+        assert_eq!(0, position.horizontal);
+    }
+
+    #[test]
+    fn down_forward() {
+        let commands = vec![
+            Command { dir: Direction::Down, val: 6 },
+            Command { dir: Direction::Forward, val: 6 }
+        ];
+
+        let position = final_position(&commands).expect("moving failed!");
+
+        assert_eq!(6, position.depth);
+        assert_eq!(6, position.horizontal);
+    }
+
+    #[test]
+    fn above_surface() {
+        let commands = Day2::parse("down 3\nup 4").expect("parsing failed!");
+        let e = final_position(&commands).err().expect("moving succeeded!");
+        assert_eq!("line 2: up 4 goes above the surface", e.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_do_commands_with_aim {
+    use super::*;
+
+    #[test]
+    fn forward() {
+        let commands = vec![
+            Command { dir: Direction::Forward, val: 5 }
+        ];
+
+        let position = final_position_with_aim(&commands).expect("moving failed!");
+
+        assert_eq!(0, position.aim);
+        assert_eq!(0, position.depth);
+        assert_eq!(5, position.horizontal);
+    }
+
+    #[test]
+    fn down() {
+        let commands = vec![
+            Command { dir: Direction::Down, val: 19 }
+        ];
+
+        let position = final_position_with_aim(&commands).expect("moving failed!");
+
+        assert_eq!(19, position.aim);
+        assert_eq!(0, position.depth);
+        assert_eq!(0, position.horizontal);
+    }
+
+    #[test]
+    fn down_up() {
+        let commands = vec![
+            Command { dir: Direction::Down, val: 19 },
+            Command { dir: Direction::Up, val: 4 }
+        ];
+
+        let position = final_position_with_aim(&commands).expect("moving failed!");
+
+        assert_eq!(15, position.aim);
+        assert_eq!(0, position.depth);
+        assert_eq!(0, position.horizontal);
+    }
+
+    #[test]
+    fn down_forward() {
+        let commands = vec![
+            Command { dir: Direction::Down, val: 6 },
+            Command { dir: Direction::Forward, val: 6 }
+        ];
+
+        let position = final_position_with_aim(&commands).expect("moving failed!");
+
+        assert_eq!(6, position.aim);
+        assert_eq!(36, position.depth);
+        assert_eq!(6, position.horizontal);
+    }
+
+    #[test]
+    fn errors() {
+        let commands = Day2::parse("up 1").expect("parsing failed!");
+        let e = final_position_with_aim(&commands).err().expect("moving succeeded!");
+        assert_eq!("line 1: up 1 aims above the surface", e.to_string());
+
+        let commands = Day2::parse("down 70000\nforward 70000").expect("parsing failed!");
+        let e = final_position_with_aim(&commands).err().expect("moving succeeded!");
+        assert_eq!("line 2: depth overflows", e.to_string());
+    }
+}
+
+// A position in terms of depth and horizontal.
+struct Position {
+    depth: u32,
+    horizontal: u32,
+    aim: u32
+}
+
+// A direction in which the submarine can travel.
+enum Direction {
+    Down,
+    Up,
+    Forward
+}
+
+#[derive(Debug)]
+enum DirectionError {
+    BadDirection
+}
+
+impl fmt::Display for DirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DirectionError::BadDirection => write!(f, "expected a direction (forward, down or up)")
+        }
+    }
+}
+
+impl error::Error for DirectionError {}
+
+impl FromStr for Direction {
+    type Err = DirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "down" { Ok(Direction::Down) }
+        else if s == "up" { Ok(Direction::Up) }
+        else if s == "forward" { Ok(Direction::Forward) }
+        else { Err(DirectionError::BadDirection) }
+    }
+}
+
+// A command for the submarine, like "down 5" or "forward 11".
+struct Command {
+    dir: Direction,
+    val: u32
+}
+
+// Trait implementation for Command so we can parse it from a string.
+impl FromStr for Command {
+    type Err = Diagnostic;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (dir, val) = data::scan("{} {}", s)?;
+        Ok(Command { dir, val })
+    }
+}
+
+fn final_position(commands: &[Command]) -> Result<Position, Error> {
+    let mut pos = Position { depth: 0, horizontal: 0, aim: 0 };
+
+    for (i, command) in commands.iter().enumerate() {
+        let line = i + 1;
+        match command.dir {
+            Direction::Down => pos.depth = pos.depth.checked_add(command.val).context(format!("line {}: depth overflows", line))?,
+            Direction::Up => pos.depth = pos.depth.checked_sub(command.val).context(format!("line {}: up {} goes above the surface", line, command.val))?,
+            Direction::Forward => pos.horizontal = pos.horizontal.checked_add(command.val).context(format!("line {}: horizontal position overflows", line))?
+        };
+    }
+
+    Ok(pos)
+}
+
+fn final_position_with_aim(commands: &[Command]) -> Result<Position, Error> {
+    let mut pos = Position { depth: 0, horizontal: 0, aim: 0 };
+
+    for (i, command) in commands.iter().enumerate() {
+        let line = i + 1;
+        match command.dir {
+            Direction::Down => pos.aim = pos.aim.checked_add(command.val).context(format!("line {}: aim overflows", line))?,
+            Direction::Up => pos.aim = pos.aim.checked_sub(command.val).context(format!("line {}: up {} aims above the surface", line, command.val))?,
+            Direction::Forward => {
+                pos.horizontal = pos.horizontal.checked_add(command.val).context(format!("line {}: horizontal position overflows", line))?;
+                pos.depth = pos.aim.checked_mul(command.val).and_then(|d| pos.depth.checked_add(d)).context(format!("line {}: depth overflows", line))?;
+            }
+        };
+    }
+
+    Ok(pos)
+}
+
+struct Day2;
+
+pub const DAY: Day = Day::new::<Day2>();
+
+impl Solution for Day2 {
+    type Input = Vec<Command>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    const DAY: u32 = 2;
+    const PART1: &'static str = "simple";
+    const PART2: &'static str = "with aim";
+
+    fn parse(input: &str) -> Result<Vec<Command>, Error> {
+        Ok(data::from_lines(input)?)
+    }
+
+    fn part1(commands: &Vec<Command>) -> Result<u32, Error> {
+        let pos = final_position(commands)?;
+        pos.depth.checked_mul(pos.horizontal).context("depth times horizontal position overflows")
+    }
+
+    fn part2(commands: &Vec<Command>) -> Result<u32, Error> {
+        let pos = final_position_with_aim(commands)?;
+        pos.depth.checked_mul(pos.horizontal).context("depth times horizontal position overflows")
+    }
+}
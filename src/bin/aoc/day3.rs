@@ -0,0 +1,271 @@
+// Advent of Code 2021
+// Day 3
+
+use aoc::{Context, Day, Error, Solution};
+
+#[cfg(test)]
+mod test_puzzles {
+    use super::*;
+    use aoc::answers;
+
+    #[test]
+    fn part1() {
+        assert_eq!(answers::expected(3, 1), Day3::part1(&aoc::input::<Day3>()).expect("part1 failed!").to_string());
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(answers::expected(3, 2), Day3::part2(&aoc::input::<Day3>()).expect("part2 failed!").to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_examples {
+    use super::*;
+    use aoc::data::Source;
+
+    #[test]
+    fn answers() {
+        aoc::check_examples::<Day3>();
+    }
+
+    #[test]
+    fn part1() {
+        let input = aoc::example::<Day3>(Source::example(3, "ex1"));
+
+        let rates = find_rates(&input.values, input.width);
+        assert_eq!(22, rates.gamma);
+        assert_eq!(9, rates.epsilon);
+    }
+
+    #[test]
+    fn part2() {
+        let input = aoc::example::<Day3>(Source::example(3, "ex1"));
+
+        let rates = find_rates_life_support(&input.values, input.width).expect("finding rates failed!");
+        assert_eq!(23, rates.oxygen);
+        assert_eq!(10, rates.co2);
+    }
+}
+
+#[cfg(test)]
+mod test_find_rates {
+    use super::*;
+
+    #[test]
+    fn all_ones() {
+        let input = vec![
+            0b111111111,
+            0b111111111,
+            0b111111111,
+            0b111111111,
+            0b111111111,
+            0b111111111
+        ];
+
+        let rates = find_rates(&input, 9);
+
+        assert_eq!(511, rates.gamma);
+        assert_eq!(0, rates.epsilon);
+    }
+
+    #[test]
+    fn all_zeros() {
+        let input = vec![
+            0b0000000,
+            0b0000000,
+            0b0000000
+        ];
+
+        let rates = find_rates(&input, 7);
+
+        assert_eq!(0, rates.gamma);
+        assert_eq!(127, rates.epsilon);
+    }
+
+    #[test]
+    fn mixed() {
+        let input = vec![
+            0b000,
+            0b111,
+            0b001,
+            0b101,
+            0b110,
+            0b000,
+            0b101
+        ];
+
+        let rates = find_rates(&input, 3);
+
+        assert_eq!(5, rates.gamma);
+        assert_eq!(2, rates.epsilon);
+    }
+
+    #[test]
+    fn no_single_value() {
+        let e = find_rates_life_support(&[0b11, 0b11], 2).err().expect("finding rates succeeded!");
+        assert_eq!("oxygen generator rating: 2 values still left after the last bit", e.to_string());
+
+        let e = find_rates_co2(&[0b10, 0b11], 2).unwrap_err();
+        assert_eq!("no values left after bit 1", e.to_string());
+    }
+
+    #[test]
+    fn too_wide() {
+        let e = Day3::parse(&"0".repeat(33)).err().expect("parsing succeeded!");
+        assert_eq!("values are 33 bits, at most 32 are supported", e.to_string());
+    }
+}
+
+// The diagnostic report: binary numbers that all have the same number of bits.
+struct DiagnosticReport {
+    values: Vec<u32>,
+    width: usize
+}
+
+struct Rates {
+    gamma: u32,
+    epsilon: u32
+}
+
+struct LifeSupportRates {
+    oxygen: u32,
+    co2: u32
+}
+
+fn most_common(input: &[u32], width: usize) -> (Vec<u32>, Vec<u32>) {
+    let mut count_ones: Vec<u32> = vec![0; width];
+    let mut count_zeros: Vec<u32> = vec![0; width];
+
+    // Find most common bits in each position.
+    for i in input {
+        let mut mask = 1;
+        for w in 0..width {
+            if i & mask == mask {
+                count_ones[w] += 1;
+            }
+            else {
+                count_zeros[w] += 1;
+            }
+
+            mask <<= 1;
+        }
+    }
+
+    (count_ones, count_zeros)
+}
+
+fn find_rates(input: &[u32], width: usize) -> Rates {
+    let (count_ones, count_zeros) = most_common(input, width);
+
+    // Calculate gamma.
+    let mut gamma = 0;
+    for w in (0..width).rev() {
+        gamma <<= 1;
+        if count_ones[w] > count_zeros[w] {
+            gamma |= 1;
+        }
+    }
+
+    // Epsilon is just gamma but NOTed,
+    // truncated to width.
+    let epsilon = !gamma;
+
+    // Generate a mask to truncate epsilon.
+    let mut mask = 0;
+    for _ in 0..width {
+        mask <<= 1;
+        mask |= 1;
+    }
+
+    let epsilon = epsilon & mask;
+
+    Rates { gamma, epsilon }
+}
+
+fn find_rates_life_support(input: &[u32], width: usize) -> Result<LifeSupportRates, Error> {
+    let oxygen = find_rates_oxygen(input, width).context("oxygen generator rating")?;
+    let co2 = find_rates_co2(input, width).context("CO2 scrubber rating")?;
+
+    Ok(LifeSupportRates { oxygen, co2 })
+}
+
+fn find_rates_with_closure(input: &[u32], width: usize, f: &dyn Fn(u32, u32) -> bool) -> Result<u32, Error> {
+    let (count_ones, count_zeros) = most_common(input, width);
+
+    let mut selected = Vec::new();
+
+    let w = width.checked_sub(1).context(format!("{} values still left after the last bit", input.len()))?;
+
+    for i in input {
+        if f(count_ones[w], count_zeros[w]) {
+            let mask = 1 << w;
+            if i & mask == mask {
+                selected.push(*i);
+            }
+        }
+        else {
+            let mask = !(1 << w);
+            if i | mask == mask {
+                selected.push(*i)
+            }
+        }
+    }
+
+    match selected.len() {
+        0 => Err(Error::msg(format!("no values left after bit {}", w))),
+        1 => Ok(selected[0]),
+        _ => find_rates_with_closure(&selected, w, f)
+    }
+}
+
+fn find_rates_oxygen(input: &[u32], width: usize) -> Result<u32, Error> {
+    find_rates_with_closure(input, width, &|x, y| x >= y)
+}
+
+fn find_rates_co2(input: &[u32], width: usize) -> Result<u32, Error> {
+    find_rates_with_closure(input, width, &|x, y| x < y)
+}
+
+struct Day3;
+
+pub const DAY: Day = Day::new::<Day3>();
+
+impl Solution for Day3 {
+    type Input = DiagnosticReport;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    const DAY: u32 = 3;
+    const PART1: &'static str = "power consumption";
+    const PART2: &'static str = "life support";
+
+    // The number of bits is taken from the first line.
+    fn parse(input: &str) -> Result<DiagnosticReport, Error> {
+        let width = input.lines().next().context("empty diagnostic report")?.trim().len();
+        if width > 32 {
+            return Err(Error::msg(format!("values are {} bits, at most 32 are supported", width)));
+        }
+
+        let mut values = Vec::new();
+        for (i, s) in input.lines().enumerate() {
+            let s = s.trim();
+            if s.len() != width {
+                return Err(Error::msg(format!("line {}: expected {} bits, found {}", i + 1, width, s.len())));
+            }
+            values.push(u32::from_str_radix(s, 2).context(format!("line {}", i + 1))?);
+        }
+
+        Ok(DiagnosticReport { values, width })
+    }
+
+    fn part1(input: &DiagnosticReport) -> Result<u32, Error> {
+        let rates = find_rates(&input.values, input.width);
+        rates.epsilon.checked_mul(rates.gamma).context("power consumption overflows")
+    }
+
+    fn part2(input: &DiagnosticReport) -> Result<u32, Error> {
+        let rates = find_rates_life_support(&input.values, input.width)?;
+        rates.oxygen.checked_mul(rates.co2).context("life support rating overflows")
+    }
+}
@@ -1,104 +1,40 @@
 // Advent of Code 2021
 // Day 4
 
-use core::iter::Iterator;
-use std::slice::Iter;
-
-use aoc::data;
+use aoc::{data, Context, Day, Error, Solution};
+use aoc::diagnostic::Diagnostic;
+use aoc::data::{FromSection, Section, Tokenizer};
 
 #[cfg(test)]
 mod test_puzzles {
+    use super::*;
+    use aoc::answers;
+
     #[test]
     fn part1() {
-        let score = super::part1();
-        assert_eq!(35670, score);
+        assert_eq!(answers::expected(4, 1), Day4::part1(&aoc::input::<Day4>()).expect("part1 failed!").to_string());
     }
 
     #[test]
     fn part2() {
-        let score = super::part2();
-        assert_eq!(22704, score);
+        assert_eq!(answers::expected(4, 2), Day4::part2(&aoc::input::<Day4>()).expect("part2 failed!").to_string());
     }
 }
 
 #[cfg(test)]
 mod test_examples {
     use super::*;
+    use aoc::data::Source;
 
     #[test]
-    fn part1() {
-        let input = vec![
-            "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1",
-            "",
-            "22 13 17 11  0",
-            "8  2 23  4 24",
-            "21  9 14 16  7",
-            "6 10  3 18  5",
-            "1 12 20 15 19",
-            "",
-            "3 15  0  2 22",
-            "9 18 13 17  5",
-            "19  8  7 25 23",
-            "20 11 10 24  4",
-            "14 21 16 12  6",
-            "",
-            "14 21 17 24  4",
-            "10 16 15  9 19",
-            "18  8 23 26 20",
-            "22 11 13  6  5",
-            "2  0 12  3  7",
-        ];
-
-        let mut input_iter = input.iter();
-
-        let calls = parse_calls(&mut input_iter);
-
-        let mut boards = Vec::new();
-        for _ in 0..3 {
-            boards.push(BingoBoard::parse(&mut input_iter))
-        }
-
-        let score = play_bingo(&calls, &mut boards);
-
-        assert_eq!(4512, score);
+    fn answers() {
+        aoc::check_examples::<Day4>();
     }
 
     #[test]
-    fn part2() {
-        let input = vec![
-            "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1",
-            "",
-            "22 13 17 11  0",
-            "8  2 23  4 24",
-            "21  9 14 16  7",
-            "6 10  3 18  5",
-            "1 12 20 15 19",
-            "",
-            "3 15  0  2 22",
-            "9 18 13 17  5",
-            "19  8  7 25 23",
-            "20 11 10 24  4",
-            "14 21 16 12  6",
-            "",
-            "14 21 17 24  4",
-            "10 16 15  9 19",
-            "18  8 23 26 20",
-            "22 11 13  6  5",
-            "2  0 12  3  7",
-        ];
-
-        let mut input_iter = input.iter();
-
-        let calls = parse_calls(&mut input_iter);
-
-        let mut boards = Vec::new();
-        for _ in 0..3 {
-            boards.push(BingoBoard::parse(&mut input_iter))
-        }
-
-        let score = play_bingo_last_winner(&calls, &mut boards);
-
-        assert_eq!(1924, score);
+    fn boards() {
+        let input = aoc::example::<Day4>(Source::example(4, "ex1"));
+        assert_eq!(3, input.1.len());
     }
 }
 
@@ -108,14 +44,7 @@ mod test_parse_calls {
 
     #[test]
     fn parse1() {
-        let input = vec![
-            "1,33,88,9,20,4",
-            ""
-        ];
-
-        let mut input_iter = input.iter();
-
-        let calls = parse_calls(&mut input_iter);
+        let calls = parse_calls("1,33,88,9,20,4").expect("parsing failed!");
 
         assert_eq!(6, calls.len());
         assert_eq!(1, calls[0]);
@@ -133,17 +62,14 @@ mod test_parse_board {
 
     #[test]
     fn parse1() {
-        let input = vec![
-            "99 20 40 60 22",
-            " 1  4 90 63 12",
-            "11 13  5  9 10",
-            "34 35 36 37 38",
-            "89 78 67 56 45",
-        ];
+        let input = "\
+99 20 40 60 22
+ 1  4 90 63 12
+11 13  5  9 10
+34 35 36 37 38
+89 78 67 56 45";
 
-        let mut input_iter = input.iter();
-
-        let board = BingoBoard::parse(&mut input_iter);
+        let board = BingoBoard::parse(input, 1).expect("parsing failed!");
 
         assert_eq!(99, board.cell(0, 0));
         assert_eq!(1, board.cell(0, 1));
@@ -175,6 +101,21 @@ mod test_parse_board {
         assert_eq!(38, board.cell(4, 3));
         assert_eq!(45, board.cell(4, 4));
     }
+
+    #[test]
+    fn errors() {
+        let e = Day4::parse("1,2\n\n1 2 3 4 5\n").err().expect("parsing succeeded!");
+        assert_eq!("section 2: missing board row", e.to_string());
+
+        let e = Day4::parse("1,2\n\n1 2 3 4 5\n1 2 x 4 5\n").err().expect("parsing succeeded!");
+        assert_eq!("section 2: 'x': invalid digit found in string\n --> line 4, column 5\n  |\n4 | 1 2 x 4 5\n  |     ^", e.to_string());
+
+        let e = Day4::parse("1,2\n\n1 2 3 4 5\n1 2  3 4\n").err().expect("parsing succeeded!");
+        assert!(e.to_string().starts_with("section 2: expected 5 numbers in board row, found 4\n"), "{}", e);
+
+        let e = Day4::parse("1,x\n").err().expect("parsing succeeded!");
+        assert_eq!("section 1: 'x' is not a number in calls: invalid digit found in string", e.to_string());
+    }
 }
 
 #[cfg(test)]
@@ -264,6 +205,7 @@ mod test_board {
     }
 }
 
+#[derive(Clone)]
 struct BingoBoard {
     // 2D grid, row-major.
     grid: [[u32; 5]; 5],
@@ -272,26 +214,37 @@ struct BingoBoard {
     marks: [[bool; 5]; 5]
 }
 
+impl FromSection for BingoBoard {
+    type Err = Error;
+
+    fn from_section(section: &Section) -> Result<BingoBoard, Error> {
+        BingoBoard::parse(section.text, section.line)
+    }
+}
+
 impl BingoBoard {
-    fn parse(input: &mut Iter<&str>) -> BingoBoard {
+    // Parses a board whose first row is on the given line of the input.
+    fn parse(input: &str, line: usize) -> Result<BingoBoard, Error> {
+        let mut lines = input.lines();
         let mut grid = [[0; 5]; 5];
 
-        for row in grid.iter_mut() {
-            let mut row_str = input.next().expect("Missing row!");
-            while row_str.trim().is_empty() {
-                row_str = input.next().expect("Missing row!");
-            }
-            
-            let mut row_iter = row_str.split_whitespace();
-            for v in row.iter_mut() {
-                let n: u32 = row_iter.next().expect("Incomplete row!")
-                                     .parse().expect("Incomplete row!");
+        for (i, row) in grid.iter_mut().enumerate() {
+            let row_str = lines.next().context("missing board row")?;
 
-                *v = n;
+            let numbers: Vec<u32> = data::from_separated(row_str, Tokenizer::whitespace()).map_err(|e| e.at_line(line + i))?;
+            if numbers.len() != row.len() {
+                let e = Diagnostic::whole_line(row_str, format!("expected {} numbers in board row, found {}", row.len(), numbers.len()));
+                return Err(Error::from(e.at_line(line + i)));
             }
+
+            row.copy_from_slice(&numbers);
         }
 
-        BingoBoard { grid, marks: [[false; 5]; 5] }
+        if lines.next().is_some() {
+            return Err(Error::msg("too many board rows"));
+        }
+
+        Ok(BingoBoard { grid, marks: [[false; 5]; 5] })
     }
 
     fn cell(&self, x: usize, y: usize) -> u32 {
@@ -353,30 +306,28 @@ impl BingoBoard {
     }
 }
 
-fn parse_calls(iter: &mut Iter<&str>) -> Vec<u32> {
-    let calls_str = iter.next().expect("No call line to parse!");
-
+fn parse_calls(calls_str: &str) -> Result<Vec<u32>, Error> {
     let mut calls = Vec::new();
-    for call in calls_str.split(',') {
-        calls.push(call.parse().expect("Non-number in call!"));
+    for call in calls_str.trim().split(',') {
+        calls.push(call.parse().context(format!("'{}' is not a number in calls", call))?);
     }
-    calls
+    Ok(calls)
 }
 
-fn play_bingo(calls: &[u32], boards: &mut [BingoBoard]) -> u32 {
+fn play_bingo(calls: &[u32], boards: &mut [BingoBoard]) -> Option<u32> {
     for call in calls {
         for board in &mut *boards {
             board.call(*call);
             if board.bingo() {
-                return board.score() * call;
+                return Some(board.score() * call);
             }
         }
     }
 
-    panic!("Nobody won bingo!");
+    None
 }
 
-fn play_bingo_last_winner(calls: &[u32], boards: &mut [BingoBoard]) -> u32 {
+fn play_bingo_last_winner(calls: &[u32], boards: &mut [BingoBoard]) -> Option<u32> {
     let mut boards_remaining = boards.len();
 
     for call in calls {
@@ -395,44 +346,47 @@ fn play_bingo_last_winner(calls: &[u32], boards: &mut [BingoBoard]) -> u32 {
 
                 // If that was the last board, return the score.
                 if boards_remaining == 0 {
-                    return board.score() * call;
+                    return Some(board.score() * call);
                 }
             }
         }
     }
 
-    panic!("Nobody won bingo!");
+    None
 }
 
-fn get_boards_and_calls(iter: &mut Iter<&str>) -> (Vec<u32>, Vec<BingoBoard>) {
-    let calls = parse_calls(iter);
-    let mut boards = Vec::new();
+struct Day4;
 
-    while iter.len() > 0 {
-        boards.push(BingoBoard::parse(iter));
-    }
+pub const DAY: Day = Day::new::<Day4>();
 
-    (calls, boards)
-}
+impl Solution for Day4 {
+    type Input = (Vec<u32>, Vec<BingoBoard>);
+    type Output1 = u32;
+    type Output2 = u32;
 
-fn get_data() -> (Vec<u32>, Vec<BingoBoard>) {
-    data::get_with_iter("data/day4.txt", &mut get_boards_and_calls)
-}
+    const DAY: u32 = 4;
+    const PART1: &'static str = "first to win";
+    const PART2: &'static str = "last to win";
 
-fn part1() -> u32 {
-    let (calls, mut boards) = get_data();
+    // The calls come first, then one section per board.
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let sections = data::split_sections(input);
+        let (calls, boards) = sections.split_first().context("no call line to parse")?;
 
-    
-    play_bingo(&calls, &mut boards)
-}
+        let calls = calls.parse_with(parse_calls).map_err(Error::msg)?;
+        let mut parsed = Vec::new();
+        for board in boards {
+            parsed.push(board.parse::<BingoBoard>().map_err(Error::msg)?);
+        }
 
-fn part2() -> u32 {
-    let (calls, mut boards) = get_data();
+        Ok((calls, parsed))
+    }
 
-    
-    play_bingo_last_winner(&calls, &mut boards)
-}
+    fn part1((calls, boards): &Self::Input) -> Result<u32, Error> {
+        play_bingo(calls, &mut boards.clone()).context("nobody won bingo")
+    }
 
-fn main() {
-    aoc::solution!(4, "first to win", "last to win");
+    fn part2((calls, boards): &Self::Input) -> Result<u32, Error> {
+        play_bingo_last_winner(calls, &mut boards.clone()).context("not every board won bingo")
+    }
 }
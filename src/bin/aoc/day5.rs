@@ -4,21 +4,23 @@
 use std::str::FromStr;
 use std::cmp::Ordering;
 
-use aoc::data;
+use aoc::{data, Day, Error, Solution};
+use aoc::diagnostic::Diagnostic;
 use aoc::drawing::{Point, Grid};
 
 #[cfg(test)]
 mod test_puzzles {
+    use super::*;
+    use aoc::answers;
+
     #[test]
     fn part1() {
-        let overlapping = super::part1();
-        assert_eq!(5373, overlapping);
+        assert_eq!(answers::expected(5, 1), Day5::part1(&aoc::input::<Day5>()).expect("part1 failed!").to_string());
     }
 
     #[test]
     fn part2() {
-        let overlapping = super::part2();
-        assert_eq!(21514, overlapping);
+        assert_eq!(answers::expected(5, 2), Day5::part2(&aoc::input::<Day5>()).expect("part2 failed!").to_string());
     }
 }
 
@@ -27,53 +29,8 @@ mod test_examples {
     use super::*;
 
     #[test]
-    fn part1() {
-        let input = vec![
-            "0,9 -> 5,9",
-            "8,0 -> 0,8",
-            "9,4 -> 3,4",
-            "2,2 -> 2,1",
-            "7,0 -> 7,4",
-            "6,4 -> 2,0",
-            "0,9 -> 2,9",
-            "3,4 -> 1,4",
-            "0,0 -> 8,8",
-            "5,5 -> 8,2"
-        ];
-
-        let mut lines = Vec::new();
-        for s in input {
-            let l = Line::from_str(s).expect("Line parsing failed!");
-            lines.push(l);
-        }
-
-        let overlapping = overlapping_lines_without_diagonal(&lines);
-        assert_eq!(5, overlapping);
-    }
-
-    #[test]
-    fn part2() {
-        let input = vec![
-            "0,9 -> 5,9",
-            "8,0 -> 0,8",
-            "9,4 -> 3,4",
-            "2,2 -> 2,1",
-            "7,0 -> 7,4",
-            "6,4 -> 2,0",
-            "0,9 -> 2,9",
-            "3,4 -> 1,4",
-            "0,0 -> 8,8",
-            "5,5 -> 8,2"
-        ];
-
-        let mut lines = Vec::new();
-        for s in input {
-            let l = Line::from_str(s).expect("Line parsing failed!");
-            lines.push(l);
-        }
-
-        let overlapping = overlapping_lines(&lines);
-        assert_eq!(12, overlapping);
+    fn answers() {
+        aoc::check_examples::<Day5>();
     }
 }
 
@@ -112,6 +69,12 @@ mod test_parse_line {
         assert_eq!(9, line.p2.x);
         assert_eq!(1, line.p1.y);
         assert_eq!(4, line.p2.y);
+     }
+
+    #[test]
+    fn parse_error() {
+        let e = Line::from_str("9,1 => 9,4").err().expect("parsing succeeded!");
+        assert_eq!((5..6, "expected ' -> '"), (e.span, e.message.as_str()));
     }
 }
 
@@ -121,34 +84,12 @@ struct Line {
     p2: Point
 }
 
-#[derive(Debug)]
-enum LineParseError {
-    BadSyntax
-}
-
 impl FromStr for Line {
-    type Err = LineParseError;
+    type Err = Diagnostic;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut s_iter = s.split(" -> ");
-
-        // First pair of coordinates.
-        let p1 = s_iter.next().ok_or(LineParseError::BadSyntax)?;
-
-        // Second pair of coordinates.
-        let p2 = s_iter.next().ok_or(LineParseError::BadSyntax)?;
-
-        let p1 = match Point::from_str(p1) {
-            Ok(p) => p,
-            Err(_) => return Err(LineParseError::BadSyntax)
-        };
-        
-        let p2 = match Point::from_str(p2) {
-            Ok(p) => p,
-            Err(_) => return Err(LineParseError::BadSyntax)
-        };
-
-        Ok(Line { p1, p2 })
+        let (x1, y1, x2, y2) = data::scan("{},{} -> {},{}", s)?;
+        Ok(Line { p1: Point::new(x1, y1), p2: Point::new(x2, y2) })
     }
 }
 
@@ -196,7 +137,7 @@ fn overlapping_lines(lines: &[Line]) -> u32 {
     let ysize = max_y(lines);
 
     // Create the grid.
-    let mut grid = Grid::new(xsize, ysize);
+    let mut grid: Grid = Grid::new(xsize, ysize);
 
     // For each line, plot on the grid.
     for line in lines {
@@ -233,18 +174,28 @@ fn overlapping_lines(lines: &[Line]) -> u32 {
     grid.count(&|n| n > 1)
 }
 
-fn part1() -> u32 {
-    let input = data::get("data/day5.txt");
+struct Day5;
 
-    overlapping_lines_without_diagonal(&input)
-}
+pub const DAY: Day = Day::new::<Day5>();
 
-fn part2() -> u32 {
-    let input = data::get("data/day5.txt");
+impl Solution for Day5 {
+    type Input = Vec<Line>;
+    type Output1 = u32;
+    type Output2 = u32;
 
-    overlapping_lines(&input)
-}
+    const DAY: u32 = 5;
+    const PART1: &'static str = "without diagonals";
+    const PART2: &'static str = "with diagonals";
+
+    fn parse(input: &str) -> Result<Vec<Line>, Error> {
+        Ok(data::from_lines(input)?)
+    }
 
-fn main() {
-    aoc::solution!(5, "without diagonals", "with diagonals");
+    fn part1(lines: &Vec<Line>) -> Result<u32, Error> {
+        Ok(overlapping_lines_without_diagonal(lines))
+    }
+
+    fn part2(lines: &Vec<Line>) -> Result<u32, Error> {
+        Ok(overlapping_lines(lines))
+    }
 }
\ No newline at end of file
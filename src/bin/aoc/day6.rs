@@ -0,0 +1,168 @@
+// Advent of Code 2021
+// Day 6
+
+
+use aoc::{data, Context, Day, Error, Solution};
+
+#[cfg(test)]
+mod test_puzzles {
+    use super::*;
+    use aoc::answers;
+
+    #[test]
+    fn part1() {
+        assert_eq!(answers::expected(6, 1), Day6::part1(&aoc::input::<Day6>()).expect("part1 failed!").to_string());
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(answers::expected(6, 2), Day6::part2(&aoc::input::<Day6>()).expect("part2 failed!").to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_examples {
+    use super::*;
+    use aoc::data::Source;
+
+    #[test]
+    fn answers() {
+        aoc::check_examples::<Day6>();
+    }
+
+    #[test]
+    fn part1() {
+        let lanternfish = aoc::example::<Day6>(Source::example(6, "ex1"));
+
+        assert_eq!(26, lanternfish_pop(&lanternfish, 18).expect("simulation failed!"));
+    }
+}
+
+#[cfg(test)]
+mod test_errors {
+    use super::*;
+
+    #[test]
+    fn bad_timer() {
+        let e = lanternfish_pop(&[3, 9], 1).unwrap_err();
+        assert_eq!("fish timer 9 is out of range (0 to 8)", e.to_string());
+
+        let e = next_pop(vec![0; 8]).unwrap_err();
+        assert_eq!("population has 8 timers, expected 9", e.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_tick {
+    use super::*;
+
+    #[test]
+    fn next_pop_1() {
+        let p = vec![0, 2, 0, 0, 0, 0, 0, 0, 0];
+        let p2 = next_pop(p).expect("tick failed!");
+        assert_eq!(9, p2.len());
+        assert_eq!(2, p2[0]);
+        assert_eq!(0, p2[1]);
+    }
+
+    #[test]
+    fn next_pop_0() {
+        let p = vec![2, 0, 0, 0, 0, 0, 0, 0, 0];
+        let p2 = next_pop(p).expect("tick failed!");
+        assert_eq!(9, p2.len());
+        assert_eq!(0, p2[0]);
+        assert_eq!(2, p2[6]);
+        assert_eq!(2, p2[8]);
+    }
+
+    #[test]
+    fn next_pop_0_1_4() {
+        let p = vec![2, 3, 0, 0, 5, 0, 0, 0, 0];
+        let p2 = next_pop(p).expect("tick failed!");
+        assert_eq!(9, p2.len());
+        assert_eq!(3, p2[0]);
+        assert_eq!(0, p2[1]);
+        assert_eq!(0, p2[2]);
+        assert_eq!(5, p2[3]);
+        assert_eq!(0, p2[4]);
+        assert_eq!(0, p2[5]);
+        assert_eq!(2, p2[6]);
+        assert_eq!(0, p2[7]);
+        assert_eq!(2, p2[8]);
+    }
+
+    #[test]
+    fn next_pop_all() {
+        let p = vec![2, 3, 2, 1, 4, 5, 3, 2, 1];
+        let p2 = next_pop(p).expect("tick failed!");
+        assert_eq!(9, p2.len());
+        assert_eq!(3, p2[0]);
+        assert_eq!(2, p2[1]);
+        assert_eq!(1, p2[2]);
+        assert_eq!(4, p2[3]);
+        assert_eq!(5, p2[4]);
+        assert_eq!(3, p2[5]);
+        assert_eq!(4, p2[6]);
+        assert_eq!(1, p2[7]);
+        assert_eq!(2, p2[8]);
+    }
+}
+
+fn next_pop(pop: Vec<u64>) -> Result<Vec<u64>, Error> {
+    // Sanity check - shouldn't ever change vector size.
+    if pop.len() != 9 {
+        return Err(Error::msg(format!("population has {} timers, expected 9", pop.len())));
+    }
+
+    let mut new_pop = vec![0; 9];
+
+    let reproducing = pop[0];
+
+    new_pop[..8].clone_from_slice(&pop[1..9]);
+
+    // Those that have reproduced tick over to 6,
+    // and produce new fish with timer 8.
+    new_pop[6] += reproducing;
+    new_pop[8] += reproducing;
+
+    Ok(new_pop)
+}
+
+fn lanternfish_pop(initial_pop: &[u32], days: u32) -> Result<u64, Error> {
+    let mut population = vec![0; 9];
+    for &p in initial_pop {
+        *population.get_mut(p as usize).context(format!("fish timer {} is out of range (0 to 8)", p))? += 1;
+    }
+
+    for day in 0..days {
+        population = next_pop(population).context(format!("on day {}", day + 1))?;
+    }
+
+    Ok(population.iter().sum())
+}
+
+struct Day6;
+
+pub const DAY: Day = Day::new::<Day6>();
+
+impl Solution for Day6 {
+    type Input = Vec<u32>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    const DAY: u32 = 6;
+    const PART1: &'static str = "80th day pop.";
+    const PART2: &'static str = "256th day pop.";
+
+    fn parse(input: &str) -> Result<Vec<u32>, Error> {
+        Ok(data::from_separated(input.trim(), ',')?)
+    }
+
+    fn part1(initial_pop: &Vec<u32>) -> Result<u64, Error> {
+        lanternfish_pop(initial_pop, 80)
+    }
+
+    fn part2(initial_pop: &Vec<u32>) -> Result<u64, Error> {
+        lanternfish_pop(initial_pop, 256)
+    }
+}
@@ -0,0 +1,117 @@
+// Advent of Code
+// Day 7
+
+
+use aoc::{data, Day, Error, Solution};
+
+#[cfg(test)]
+mod test_puzzles {
+    use super::*;
+    use aoc::answers;
+
+    #[test]
+    fn part1() {
+        assert_eq!(answers::expected(7, 1), Day7::part1(&aoc::input::<Day7>()).expect("part1 failed!").to_string());
+    }
+
+    #[test]
+    fn part2() {
+        assert_eq!(answers::expected(7, 2), Day7::part2(&aoc::input::<Day7>()).expect("part2 failed!").to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_examples {
+    use super::*;
+    use aoc::data::Source;
+
+    #[test]
+    fn answers() {
+        aoc::check_examples::<Day7>();
+    }
+
+    #[test]
+    fn part1() {
+        let crabs = aoc::example::<Day7>(Source::example(7, "ex1"));
+
+        let (pos, _) = min_fuel_position_linear(&crabs);
+        assert_eq!(2, pos);
+    }
+
+    #[test]
+    fn part2() {
+        let crabs = aoc::example::<Day7>(Source::example(7, "ex1"));
+
+        let (pos, _) = min_fuel_position_increasing(&crabs);
+        assert_eq!(5, pos);
+    }
+}
+
+fn max_pos(crabs: &[i32]) -> i32 {
+    let mut pos = 0;
+    for &c in crabs {
+        if c > pos {
+            pos = c;
+        }
+    }
+
+    pos
+}
+
+fn min_fuel_position_linear(crabs: &[i32]) -> (i32, i32) {
+    min_fuel_position(crabs, &|dist| dist)
+}
+
+fn min_fuel_position_increasing(crabs: &[i32]) -> (i32, i32) {
+    min_fuel_position(crabs, &|dist| ((dist) * (dist+1)) / 2)
+}
+
+fn min_fuel_position(crabs: &[i32], f_pos: &dyn Fn(i32) -> i32) -> (i32, i32) {
+    let max = max_pos(crabs);
+
+    // Iterate over each position and find the minimum.
+    let mut min = i32::MAX;
+    let mut min_pos = 0;
+
+    for p in 0..(max+1) {
+        let mut sum = 0;
+        for &c in crabs {
+            sum += f_pos((c - p).abs());
+        }
+
+        if sum < min {
+            min = sum;
+            min_pos = p;
+        }
+    }
+
+    (min_pos, min)
+}
+
+struct Day7;
+
+pub const DAY: Day = Day::new::<Day7>();
+
+impl Solution for Day7 {
+    type Input = Vec<i32>;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    const DAY: u32 = 7;
+    const PART1: &'static str = "linear";
+    const PART2: &'static str = "triangular";
+
+    fn parse(input: &str) -> Result<Vec<i32>, Error> {
+        Ok(data::from_separated(input.trim(), ',')?)
+    }
+
+    fn part1(crabs: &Vec<i32>) -> Result<i32, Error> {
+        let (_position, fuel) = min_fuel_position_linear(crabs);
+        Ok(fuel)
+    }
+
+    fn part2(crabs: &Vec<i32>) -> Result<i32, Error> {
+        let (_position, fuel) = min_fuel_position_increasing(crabs);
+        Ok(fuel)
+    }
+}
@@ -3,44 +3,62 @@
 
 use std::collections::{HashSet, HashMap};
 use std::{iter::FromIterator, slice::Iter};
+use std::time::Duration;
+
+use aoc::{Context, Day, Error, Solution};
+use aoc::diagnostic::Diagnostic;
 
 #[cfg(test)]
 mod test_puzzles {
+    use super::*;
+    use aoc::answers;
+
     #[test]
     fn part1() {
-        assert_eq!(375, super::part1());
+        assert_eq!(answers::expected(8, 1), Day8::part1(&aoc::input::<Day8>()).expect("part1 failed!").to_string());
     }
 
     #[test]
     fn part2() {
-        assert_eq!(1019355, super::part2());
+        assert_eq!(answers::expected(8, 2), Day8::part2(&aoc::input::<Day8>()).expect("part2 failed!").to_string());
     }
 }
 
 #[cfg(test)]
 mod test_examples {
     use super::*;
-    use std::str::FromStr;
 
     #[test]
-    fn part1() {
-        let input = vec![
-            "fdgacbe cefdb cefbgd gcbe",
-            "fcgedb cgb dgebacf gc",
-            "cg cg fdcagb cbg",
-            "efabcd cedba gadfec cb",
-            "gecf egdcabf bgf bfgea",
-            "gebdcfa ecba ca fadegcb",
-            "cefg dcbef fcge gbcadfe",
-            "ed bcgafe cdgba cbgef",
-            "gbdfcae bgc cg cgb",
-            "fgae cfgab fg bagce"
-        ];
-
-        let input: Vec<String> = input.iter().map(|s| String::from_str(s).expect("Parse error!")).collect();
-
-        let count = count_segments(&input, &[2, 4, 3, 7]);
-        assert_eq!(26, count);
+    fn answers() {
+        aoc::check_examples::<Day8>();
+    }
+
+    #[test]
+    fn unresolved_display() {
+        let e = SegmentDisplay::init().digit("ab").unwrap_err();
+        assert_eq!("segment has 7 possible wires, not 1", e.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    const ENTRY: &str = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+
+    #[test]
+    fn errors() {
+        let lines = [ENTRY, "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab cdfeb fcadb"];
+        let e = get_segments(&mut lines.iter()).unwrap_err();
+        assert_eq!((2, 71..71, "missing ' | ' before output"), (e.line, e.span, e.message.as_str()));
+
+        let lines = ["dab cefabd | cdfeb fcadb cdfeb cdbaf"];
+        let e = get_segments(&mut lines.iter()).unwrap_err();
+        assert_eq!((1..11, "expected 10 patterns, found 2"), (e.span, e.message.as_str()));
+
+        let lines = [&ENTRY[..78]];
+        let e = get_segments(&mut lines.iter()).unwrap_err();
+        assert_eq!((62..79, "expected 4 output digits, found 3"), (e.span, e.message.as_str()));
     }
 }
 
@@ -102,7 +120,7 @@ struct Segment {
 
 impl Segment {
     fn new(s: &str) -> Segment {
-        return Segment { possibles: HashSet::from_iter(s.chars()) }
+        Segment { possibles: HashSet::from_iter(s.chars()) }
     }
 
     fn len(&self) -> usize {
@@ -315,15 +333,17 @@ impl SegmentDisplay {
         v
     }
 
-    fn digit(&self, pattern: &str) -> Option<u32> {
+    // The digit the pattern shows, if any. Only a display with each segment
+    // narrowed down to a single wire can say.
+    fn digit(&self, pattern: &str) -> Result<Option<u32>, Error> {
         // Get the lit segments.
         let mut lit = HashSet::new();
         for c in pattern.chars() {
             for (k, v) in &self.segments {
                 if v.len() != 1 {
-                    panic!("More than one possibility!");
+                    return Err(Error::msg(format!("segment has {} possible wires, not 1", v.len())));
                 }
-                let c_seg = v.possibles.iter().last().expect("No value!");
+                let c_seg = v.possibles.iter().last().context("segment has no wire")?;
                 if c == *c_seg {
                     lit.insert(*k);
                     break;
@@ -335,14 +355,14 @@ impl SegmentDisplay {
 
         for (val, comb) in valid_combos.into_iter().enumerate() {
             if comb == lit {
-                return Some(val as u32);
+                return Ok(Some(val as u32));
             }
         }
-        None
+        Ok(None)
     }
 
-    fn valid(&self, pattern: &str) -> bool {
-        self.digit(pattern).is_some()
+    fn valid(&self, pattern: &str) -> Result<bool, Error> {
+        Ok(self.digit(pattern)?.is_some())
     }
 }
 
@@ -358,7 +378,7 @@ fn count_segments(segments: &[String], num_segments: &[u32]) -> u32 {
     count
 }
 
-fn get_segment_config(patterns: &[String]) -> SegmentDisplay {
+fn get_segment_config(patterns: &[String]) -> Result<Option<SegmentDisplay>, Error> {
     // First exclude down to the minimal.
     
     let mut seg = SegmentDisplay::init();
@@ -370,73 +390,103 @@ fn get_segment_config(patterns: &[String]) -> SegmentDisplay {
     for d in seg.permutations() {
         let mut found = true;
         for i in patterns {
-            if !d.valid(i) {
+            if !d.valid(i)? {
                 found = false;
             }
         }
 
         if found {
-            return d;
+            return Ok(Some(d));
         }
     }
 
-    panic!("Didn't find a configuration!");
+    Ok(None)
 }
 
-fn get_output(patterns: &[String], output_patterns: &[String]) -> u32 {
+fn get_output(patterns: &[String], output_patterns: &[String]) -> Result<u32, Error> {
     // Get the segment display configuration.
-    let seg = get_segment_config(patterns);
+    let seg = get_segment_config(patterns)?.context("no segment configuration matches the patterns")?;
 
     // Get each output digit.
     let mut val = 0;
     for pattern in output_patterns {
         val *= 10;
-        val += seg.digit(pattern).expect("Didn't decode!");
+        val += seg.digit(pattern)?.context(format!("could not decode output '{}'", pattern))?;
     }
 
-    val
+    Ok(val)
 }
 
-fn get_segments(i: &mut Iter<&str>) -> Vec<(Vec<String>, Vec<String>)> {
+// The ten unique signal patterns, and the four output patterns.
+type Entry = (Vec<String>, Vec<String>);
+
+// Each line is ten patterns, then " | ", then the four output digits.
+fn get_segments(i: &mut Iter<&str>) -> Result<Vec<Entry>, Diagnostic> {
     let mut v = Vec::new();
 
-    for line in i {
-        let mut iter = line.split(" | ");
-        let patterns = iter.next().expect("Parse ereor!");
-        let patterns = patterns.split_whitespace().map(String::from).collect();
-        let output = iter.next().expect("Parse error!");
-        let output = output.split_whitespace().map(String::from).collect();
+    for (n, line) in i.enumerate() {
+        let end = line.chars().count() + 1;
+        let (patterns, output) = line.split_once(" | ").ok_or_else(|| Diagnostic::new(line, end..end, "missing ' | ' before output").at_line(n + 1))?;
+
+        let patterns: Vec<String> = patterns.split_whitespace().map(String::from).collect();
+        if patterns.len() != 10 {
+            let span = 1..patterns_end(line);
+            return Err(Diagnostic::new(line, span, format!("expected 10 patterns, found {}", patterns.len())).at_line(n + 1));
+        }
+
+        let output: Vec<String> = output.split_whitespace().map(String::from).collect();
+        if output.len() != 4 {
+            let span = patterns_end(line) + 3..end;
+            return Err(Diagnostic::new(line, span, format!("expected 4 output digits, found {}", output.len())).at_line(n + 1));
+        }
 
         v.push((patterns, output));
     }
 
-    v
+    Ok(v)
 }
 
-fn get_data() -> Vec<(Vec<String>, Vec<String>)> {
-    aoc::data::get_with_iter("data/day8.txt", &mut get_segments)
+// The column just after the patterns, where " | " starts.
+fn patterns_end(line: &str) -> usize {
+    line.find(" | ").map_or(0, |i| line[..i].chars().count()) + 1
 }
 
-fn part1() -> u32 {
-    let input = get_data();
-    let mut outputs = Vec::new();
-    for mut i in input {
-        outputs.append(&mut i.1);
+struct Day8;
+
+pub const DAY: Day = Day::new::<Day8>();
+
+impl Solution for Day8 {
+    type Input = Vec<Entry>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    const DAY: u32 = 8;
+    const PART1: &'static str = "count 1, 4, 7, 8";
+    const PART2: &'static str = "sum of output values";
+
+    // The permutation search can take minutes on an awkward input.
+    const TIMEOUT: Option<Duration> = Some(Duration::from_secs(60));
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let lines: Vec<&str> = input.lines().collect();
+        Ok(get_segments(&mut lines.iter())?)
     }
 
-    count_segments(&outputs, &[2, 4, 3, 7])
-}
+    fn part1(input: &Self::Input) -> Result<u32, Error> {
+        let mut outputs = Vec::new();
+        for i in input {
+            outputs.extend_from_slice(&i.1);
+        }
 
-fn part2() -> u32 {
-    let input = get_data();
-    let mut result = 0;
-    for (patterns, output) in input {
-        result += get_output(&patterns, &output);
+        Ok(count_segments(&outputs, &[2, 4, 3, 7]))
     }
 
-    result
-}
+    fn part2(input: &Self::Input) -> Result<u32, Error> {
+        let mut result = 0;
+        for (i, (patterns, output)) in input.iter().enumerate() {
+            result += get_output(patterns, output).context(format!("entry {}", i + 1))?;
+        }
 
-fn main() {
-    aoc::solution!(8, "count 1, 4, 7, 8", "sum of output values");
+        Ok(result)
+    }
 }
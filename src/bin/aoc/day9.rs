@@ -1,38 +1,46 @@
 // Advent of Code 2021
 // Day 9
 
-use core::slice::Iter;
 use std::cmp::Reverse;
+
+use aoc::{data, Day, Error, Solution};
 use aoc::drawing::{Grid, Point};
 
 #[cfg(test)]
 mod test_puzzles {
+    use super::*;
+    use aoc::answers;
+
     #[test]
     fn part1() {
-        assert_eq!(524, super::part1());
+        assert_eq!(answers::expected(9, 1), Day9::part1(&aoc::input::<Day9>()).expect("part1 failed!").to_string());
     }
 
     #[test]
     fn part2() {
-        assert_eq!(1235430, super::part2());
+        assert_eq!(answers::expected(9, 2), Day9::part2(&aoc::input::<Day9>()).expect("part2 failed!").to_string());
     }
 }
 
 #[cfg(test)]
 mod test_examples {
     use super::*;
+    use aoc::data::Source;
 
     #[test]
-    fn part1() {
-        let input = vec![
-            "2199943210",
-            "3987894921",
-            "9856789892",
-            "8767896789",
-            "9899965678",
-        ];
+    fn answers() {
+        aoc::check_examples::<Day9>();
+    }
 
-        let grid = get_grid(&mut input.iter());
+    #[test]
+    fn too_few_basins() {
+        let grid = Day9::parse("191\n999\n").expect("parsing failed!");
+        assert_eq!("need at least 3 basins, found 2", Day9::part2(&grid).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn part1() {
+        let grid = aoc::example::<Day9>(Source::example(9, "ex1"));
         let local_minima = local_minima(&grid);
 
         assert_eq!(4, local_minima.len());
@@ -44,15 +52,7 @@ mod test_examples {
 
     #[test]
     fn part2() {
-        let input = vec![
-            "2199943210",
-            "3987894921",
-            "9856789892",
-            "8767896789",
-            "9899965678",
-        ];
-
-        let grid = get_grid(&mut input.iter());
+        let grid = aoc::example::<Day9>(Source::example(9, "ex1"));
         let basins = get_basins(&grid);
 
         assert_eq!(4, basins.len());
@@ -69,15 +69,15 @@ mod test_basins {
 
     #[test]
     fn top_left() {
-        let input = vec![
+        let input = [
             "2199943210",
             "3987894921",
             "9856789892",
             "8767896789",
-            "9899965678",
+            "9899965678"
         ];
 
-        let grid = get_grid(&mut input.iter());
+        let grid = data::digit_grid(&input.join("\n")).expect("parsing failed!");
         let basin = get_basin(&grid, Point::new(1, 0));
 
         assert_eq!(3, basin.len());
@@ -85,15 +85,15 @@ mod test_basins {
 
     #[test]
     fn top_right() {
-        let input = vec![
+        let input = [
             "2199943210",
             "3987894921",
             "9856789892",
             "8767896789",
-            "9899965678",
+            "9899965678"
         ];
 
-        let grid = get_grid(&mut input.iter());
+        let grid = data::digit_grid(&input.join("\n")).expect("parsing failed!");
         let basin = get_basin(&grid, Point::new(9, 0));
 
         assert_eq!(9, basin.len());
@@ -136,27 +136,12 @@ fn is_minimum(v: &u32, n: &[u32]) -> bool {
     true
 }
 
-fn get_grid(i: &mut Iter<&str>) -> Grid {
-    let mut grid: Vec<Vec<u32>> = Vec::new();
-    for s in i {
-        let mut v = Vec::new();
-        for c in s.chars() {
-            let n = format!("{}", c).parse().expect("Parse error!");
-            v.push(n);
-        }
-        grid.push(v);
-    }
-
-    Grid::from_array(grid)
-}
-
 fn local_minima(g: &Grid) -> Vec<Point> {
     let mut minima = Vec::new();
 
-    for p in g.points() {
+    for p in g.iter_points() {
         let val = g.get(&p);
-        let neighbours = g.neighbours(&p);
-        let neighbours: Vec<u32> = neighbours.iter().map(|n| g.get(n)).collect();
+        let neighbours: Vec<u32> = g.iter_neighbours(&p).map(|n| g.get(&n)).collect();
 
         if is_minimum(&val, &neighbours) {
             minima.push(p);
@@ -191,7 +176,7 @@ fn get_basin(g: &Grid, starting_point: Point) -> Vec<Point> {
                     visited.push(*p);
                 }
                 
-                let neighbours = g.neighbours(p);
+                let neighbours = g.iter_neighbours(p);
                 for n in neighbours {
                     if !visited.contains(&n) {
                         next_to_visit.push(n);
@@ -207,31 +192,43 @@ fn get_basin(g: &Grid, starting_point: Point) -> Vec<Point> {
     visited
 }
 
-fn get_data() -> Grid {
-    aoc::data::get_with_iter("data/day9.txt", &mut get_grid)
-}
+struct Day9;
 
-fn part1() -> u32 {
-    let grid = get_data();
-    let minima = local_minima(&grid);
-    
-    let mut risk_level = 0;
-    for m in minima {
-        risk_level += grid.get(&m) + 1;
+pub const DAY: Day = Day::new::<Day9>();
+
+impl Solution for Day9 {
+    type Input = Grid;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    const DAY: u32 = 9;
+    const PART1: &'static str = "# local minima";
+    const PART2: &'static str = "largest basins (product)";
+
+    fn parse(input: &str) -> Result<Grid, Error> {
+        Ok(data::digit_grid(input)?)
     }
 
-    risk_level
-}
+    fn part1(grid: &Grid) -> Result<u32, Error> {
+        let minima = local_minima(grid);
 
-fn part2() -> u32 {
-    let grid = get_data();
-    let mut basins = get_basins(&grid);
-    basins.sort_by_key(|b| Reverse(b.len()));
+        let mut risk_level = 0;
+        for m in minima {
+            risk_level += grid.get(&m) + 1;
+        }
 
-    let result = basins[0].len() * basins[1].len() * basins[2].len();
-    result as u32
-}
+        Ok(risk_level)
+    }
 
-fn main() {
-    aoc::solution!(9, "# local minima", "largest basins (product)");
+    fn part2(grid: &Grid) -> Result<u32, Error> {
+        let mut basins = get_basins(grid);
+        basins.sort_by_key(|b| Reverse(b.len()));
+
+        if basins.len() < 3 {
+            return Err(Error::msg(format!("need at least 3 basins, found {}", basins.len())));
+        }
+
+        let result = basins[0].len() * basins[1].len() * basins[2].len();
+        Ok(result as u32)
+    }
 }
\ No newline at end of file
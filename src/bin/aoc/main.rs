@@ -0,0 +1,632 @@
+// Advent of Code 2021
+// Runner for all registered days.
+
+use std::env;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use std::time::{Duration, Instant};
+
+use aoc::{Day, Options, Report};
+use aoc::answers::{self, Answers, AnswersError, Status};
+use aoc::bench::{Bench, Limit};
+use aoc::fetch::{self, Config, Fetched};
+use aoc::memory;
+use aoc::perf::{self, History, PerfError, Record};
+
+#[global_allocator]
+static ALLOCATOR: memory::Counting = memory::Counting;
+
+mod day1;
+mod day2;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod day8;
+mod day9;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+
+const DAYS: &[Day] = &[
+    day1::DAY,
+    day2::DAY,
+    day3::DAY,
+    day4::DAY,
+    day5::DAY,
+    day6::DAY,
+    day7::DAY,
+    day8::DAY,
+    day9::DAY,
+    day10::DAY,
+    day11::DAY,
+    day12::DAY,
+    day13::DAY,
+    day14::DAY,
+    day15::DAY,
+    day16::DAY,
+];
+
+const USAGE: &str = "\
+usage: aoc run <day> [options]
+       aoc run --all [options]
+       aoc list
+       aoc fetch <day>
+       aoc perf compare [--baseline <name>] [--threshold <percent>]
+
+options:
+    --part <1|2>        only run the given part
+    --input <path>      read the day's input from path, or stdin for '-'
+                        (default: $AOC_INPUT_DIR/dayN.txt, then data/dayN.txt)
+    --examples          run the day's examples from examples/dayN/ instead,
+                        checking their answers
+    --bench             benchmark each part (3 warm-up runs, then 1 second of timed runs)
+    --warmup <n>        benchmark with n warm-up runs
+    --iterations <n>    benchmark with n timed runs
+    --time <seconds>    benchmark with timed runs for the given time
+    --timeout <seconds> give up on any part still running after the given time
+                        (default: none, or the day's own limit)
+    --memory            report allocations, total bytes allocated and peak
+                        bytes in use for each part
+    --threads <n>       run up to n days at once (default: one per CPU,
+                        or one when benchmarking)
+    --label <name>      record this run's timings under the given name
+    --no-history        don't record this run's timings
+
+perf compare options:
+    --baseline <name>   compare with the latest run with this label or git revision
+                        (default: the run before the latest)
+    --threshold <n>     flag parts more than n percent slower (default: 10)
+
+fetch downloads a day's input into data/cache/2021/dayN.txt (see README for settings)";
+
+#[cfg(test)]
+mod test_parse_args {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn list() {
+        assert_eq!(Ok(Command::List), parse_args(&args("list")));
+    }
+
+    fn part(p: u32) -> Options {
+        Options { part: Some(p), ..Options::default() }
+    }
+
+    fn bench(warmup: u32, limit: Limit) -> Options {
+        Options { bench: Some(Bench { warmup, limit }), ..Options::default() }
+    }
+
+    #[test]
+    fn run_day() {
+        assert_eq!(Ok(Command::Run(Some(5), Options::default())), parse_args(&args("run 5")));
+    }
+
+    #[test]
+    fn run_day_part() {
+        assert_eq!(Ok(Command::Run(Some(5), part(2))), parse_args(&args("run 5 --part 2")));
+        assert_eq!(Ok(Command::Run(Some(5), part(2))), parse_args(&args("run --part 2 5")));
+    }
+
+    #[test]
+    fn run_all() {
+        assert_eq!(Ok(Command::Run(None, Options::default())), parse_args(&args("run --all")));
+    }
+
+    #[test]
+    fn run_input() {
+        let options = Options { input: Some(String::from("-")), ..Options::default() };
+        assert_eq!(Ok(Command::Run(Some(3), options)), parse_args(&args("run 3 --input -")));
+        assert_eq!(Err(ArgsError::InputWithAll), parse_args(&args("run --all --input x.txt")));
+    }
+
+    #[test]
+    fn run_examples() {
+        let options = Options { examples: true, ..Options::default() };
+        assert_eq!(Ok(Command::Run(None, options)), parse_args(&args("run --all --examples")));
+        assert_eq!(Err(ArgsError::InputWithExamples), parse_args(&args("run 3 --examples --input x.txt")));
+    }
+
+    #[test]
+    fn run_bench() {
+        assert_eq!(Ok(Command::Run(Some(1), bench(3, Limit::Time(Duration::from_secs(1))))), parse_args(&args("run 1 --bench")));
+        assert_eq!(Ok(Command::Run(Some(1), bench(3, Limit::Iterations(20)))), parse_args(&args("run 1 --iterations 20")));
+        assert_eq!(Ok(Command::Run(None, bench(0, Limit::Time(Duration::from_millis(500))))), parse_args(&args("run --all --time 0.5 --warmup 0")));
+    }
+
+    #[test]
+    fn run_timeout() {
+        let options = Options { timeout: Some(Duration::from_secs(5)), ..Options::default() };
+        assert_eq!(Ok(Command::Run(Some(12), options)), parse_args(&args("run 12 --timeout 5")));
+        assert_eq!(Err(ArgsError::InvalidValue(String::from("--timeout"), String::from("-1"))), parse_args(&args("run 12 --timeout -1")));
+    }
+
+    #[test]
+    fn run_memory() {
+        let options = Options { memory: true, ..Options::default() };
+        assert_eq!(Ok(Command::Run(Some(15), options)), parse_args(&args("run 15 --memory")));
+    }
+
+    #[test]
+    fn run_threads() {
+        let options = Options { threads: Some(4), ..Options::default() };
+        assert_eq!(Ok(Command::Run(None, options)), parse_args(&args("run --all --threads 4")));
+        assert_eq!(Err(ArgsError::InvalidValue(String::from("--threads"), String::from("0"))), parse_args(&args("run --all --threads 0")));
+    }
+
+    #[test]
+    fn run_history() {
+        let options = Options { label: Some(String::from("before")), ..Options::default() };
+        assert_eq!(Ok(Command::Run(Some(1), options)), parse_args(&args("run 1 --label before")));
+
+        let options = Options { no_history: true, ..Options::default() };
+        assert_eq!(Ok(Command::Run(Some(1), options)), parse_args(&args("run 1 --no-history")));
+
+        assert_eq!(Err(ArgsError::InvalidValue(String::from("--label"), String::from("a,b"))), parse_args(&args("run 1 --label a,b")));
+    }
+
+    #[test]
+    fn perf_compare() {
+        assert_eq!(Ok(Command::Compare(None, 10.0)), parse_args(&args("perf compare")));
+        assert_eq!(Ok(Command::Compare(Some(String::from("before")), 5.0)), parse_args(&args("perf compare --baseline before --threshold 5")));
+        assert_eq!(Err(ArgsError::UnknownCommand(String::from("perf show"))), parse_args(&args("perf show")));
+
+        for bad in ["-5", "NaN", "inf", "x"] {
+            let e = parse_args(&args(&format!("perf compare --threshold {}", bad)));
+            assert_eq!(Err(ArgsError::InvalidValue(String::from("--threshold"), String::from(bad))), e);
+        }
+    }
+
+    #[test]
+    fn fetch() {
+        assert_eq!(Ok(Command::Fetch(7)), parse_args(&args("fetch 7")));
+        assert_eq!(Err(ArgsError::MissingDay), parse_args(&args("fetch")));
+        assert_eq!(Err(ArgsError::InvalidDay(String::from("26"))), parse_args(&args("fetch 26")));
+        assert_eq!(Err(ArgsError::UnknownOption(String::from("--all"))), parse_args(&args("fetch 7 --all")));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Err(ArgsError::MissingCommand), parse_args(&args("")));
+        assert_eq!(Err(ArgsError::UnknownCommand(String::from("walk"))), parse_args(&args("walk")));
+        assert_eq!(Err(ArgsError::MissingDay), parse_args(&args("run")));
+        assert_eq!(Err(ArgsError::InvalidDay(String::from("x"))), parse_args(&args("run x")));
+        assert_eq!(Err(ArgsError::InvalidPart(String::from("3"))), parse_args(&args("run 1 --part 3")));
+        assert_eq!(Err(ArgsError::MissingValue(String::from("--part"))), parse_args(&args("run 1 --part")));
+        assert_eq!(Err(ArgsError::InvalidValue(String::from("--time"), String::from("soon"))), parse_args(&args("run 1 --time soon")));
+        assert_eq!(Err(ArgsError::DayAndAll), parse_args(&args("run 1 --all")));
+    }
+}
+
+#[derive(PartialEq, Debug)]
+enum Command {
+    // Run the given day (or all days if None).
+    Run(Option<u32>, Options),
+    List,
+
+    // Compare the latest run's timings with a baseline (or the previous run),
+    // flagging parts slower by more than the threshold percentage.
+    Compare(Option<String>, f64),
+
+    // Download the given day's input, unless it's already cached.
+    Fetch(u32)
+}
+
+#[derive(PartialEq, Eq, Debug)]
+enum ArgsError {
+    MissingCommand,
+    UnknownCommand(String),
+    UnknownOption(String),
+    MissingDay,
+    InvalidDay(String),
+    DayAndAll,
+    InputWithAll,
+    InputWithExamples,
+    InvalidPart(String),
+    MissingValue(String),
+    InvalidValue(String, String)
+}
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArgsError::MissingCommand => write!(f, "no command given"),
+            ArgsError::UnknownCommand(c) => write!(f, "unknown command '{}'", c),
+            ArgsError::UnknownOption(o) => write!(f, "unknown option '{}'", o),
+            ArgsError::MissingDay => write!(f, "no day given (use --all to run every day)"),
+            ArgsError::InvalidDay(d) => write!(f, "'{}' is not a day number", d),
+            ArgsError::DayAndAll => write!(f, "cannot give both a day and --all"),
+            ArgsError::InputWithAll => write!(f, "--input can only be used with a single day"),
+            ArgsError::InputWithExamples => write!(f, "cannot give both --input and --examples"),
+            ArgsError::InvalidPart(p) => write!(f, "'{}' is not a part (expected 1 or 2)", p),
+            ArgsError::MissingValue(o) => write!(f, "{} needs a value", o),
+            ArgsError::InvalidValue(o, v) => write!(f, "'{}' is not a valid value for {}", v, o)
+        }
+    }
+}
+
+// Gets the value following an option.
+fn value<'a>(option: &str, iter: &mut dyn Iterator<Item = &'a String>) -> Result<&'a String, ArgsError> {
+    iter.next().ok_or_else(|| ArgsError::MissingValue(option.to_string()))
+}
+
+// Gets and parses the value following an option.
+fn parsed_value<T: FromStr>(option: &str, iter: &mut dyn Iterator<Item = &String>) -> Result<T, ArgsError> {
+    let v = value(option, iter)?;
+    v.parse().map_err(|_| ArgsError::InvalidValue(option.to_string(), v.clone()))
+}
+
+// Gets the value following an option as a percentage, which can't be negative.
+fn percentage(option: &str, iter: &mut dyn Iterator<Item = &String>) -> Result<f64, ArgsError> {
+    let v = value(option, iter)?;
+    v.parse().ok().filter(|p: &f64| p.is_finite() && *p >= 0.0).ok_or_else(|| ArgsError::InvalidValue(option.to_string(), v.clone()))
+}
+
+// Gets the value following an option as a duration in (possibly fractional) seconds.
+fn seconds(option: &str, iter: &mut dyn Iterator<Item = &String>) -> Result<Duration, ArgsError> {
+    let t: f64 = parsed_value(option, iter)?;
+    Duration::try_from_secs_f64(t).map_err(|_| ArgsError::InvalidValue(option.to_string(), t.to_string()))
+}
+
+fn parse_args(args: &[String]) -> Result<Command, ArgsError> {
+    let mut iter = args.iter();
+
+    let command = iter.next().ok_or(ArgsError::MissingCommand)?;
+
+    match command.as_str() {
+        "list" => Ok(Command::List),
+        "run" => {
+            let mut day = None;
+            let mut all = false;
+            let mut options = Options::default();
+
+            while let Some(arg) = iter.next() {
+                match arg.as_str() {
+                    "--all" => all = true,
+                    "--part" => {
+                        let p = value(arg, &mut iter)?;
+                        options.part = match p.as_str() {
+                            "1" => Some(1),
+                            "2" => Some(2),
+                            _ => return Err(ArgsError::InvalidPart(p.clone()))
+                        };
+                    },
+                    "--input" => {
+                        options.input = Some(value(arg, &mut iter)?.clone());
+                    },
+                    "--examples" => options.examples = true,
+                    "--bench" => {
+                        options.bench.get_or_insert_with(Bench::default);
+                    },
+                    "--warmup" => {
+                        options.bench.get_or_insert_with(Bench::default).warmup = parsed_value(arg, &mut iter)?;
+                    },
+                    "--iterations" => {
+                        let n = parsed_value(arg, &mut iter)?;
+                        options.bench.get_or_insert_with(Bench::default).limit = Limit::Iterations(n);
+                    },
+                    "--threads" => {
+                        let n: usize = parsed_value(arg, &mut iter)?;
+                        if n == 0 {
+                            return Err(ArgsError::InvalidValue(arg.clone(), n.to_string()));
+                        }
+                        options.threads = Some(n);
+                    },
+                    "--memory" => options.memory = true,
+                    "--no-history" => options.no_history = true,
+                    "--label" => {
+                        let label = value(arg, &mut iter)?;
+                        if label.contains(',') {
+                            return Err(ArgsError::InvalidValue(arg.clone(), label.clone()));
+                        }
+                        options.label = Some(label.clone());
+                    },
+                    "--time" => {
+                        let t = seconds(arg, &mut iter)?;
+                        options.bench.get_or_insert_with(Bench::default).limit = Limit::Time(t);
+                    },
+                    "--timeout" => {
+                        options.timeout = Some(seconds(arg, &mut iter)?);
+                    },
+                    a if a.starts_with("--") => return Err(ArgsError::UnknownOption(a.to_string())),
+                    a => {
+                        let d = a.parse().or(Err(ArgsError::InvalidDay(a.to_string())))?;
+                        day = Some(d);
+                    }
+                }
+            }
+
+            match (day, all) {
+                (Some(_), true) => Err(ArgsError::DayAndAll),
+                (None, true) if options.input.is_some() => Err(ArgsError::InputWithAll),
+                _ if options.input.is_some() && options.examples => Err(ArgsError::InputWithExamples),
+                (None, false) => Err(ArgsError::MissingDay),
+                (day, _) => Ok(Command::Run(day, options))
+            }
+        },
+        "perf" => {
+            match iter.next().map(String::as_str) {
+                Some("compare") => (),
+                Some(c) => return Err(ArgsError::UnknownCommand(format!("perf {}", c))),
+                None => return Err(ArgsError::UnknownCommand(String::from("perf")))
+            }
+
+            let mut baseline = None;
+            let mut threshold = 10.0;
+
+            while let Some(arg) = iter.next() {
+                match arg.as_str() {
+                    "--baseline" => baseline = Some(value(arg, &mut iter)?.clone()),
+                    "--threshold" => threshold = percentage(arg, &mut iter)?,
+                    a => return Err(ArgsError::UnknownOption(a.to_string()))
+                }
+            }
+
+            Ok(Command::Compare(baseline, threshold))
+        },
+        "fetch" => {
+            let d = iter.next().ok_or(ArgsError::MissingDay)?;
+            let day = d.parse().ok().filter(|n| (1..=25).contains(n)).ok_or_else(|| ArgsError::InvalidDay(d.clone()))?;
+
+            match iter.next() {
+                Some(a) => Err(ArgsError::UnknownOption(a.clone())),
+                None => Ok(Command::Fetch(day))
+            }
+        },
+        c => Err(ArgsError::UnknownCommand(c.to_string()))
+    }
+}
+
+fn list() {
+    for d in DAYS {
+        println!("day{:<4} {:25} {}", d.day, d.part1, d.part2);
+    }
+}
+
+// Loads the known answers. A missing file just means every result is UNKNOWN.
+fn load_answers() -> Answers {
+    match Answers::load(answers::DEFAULT_FILE) {
+        Ok(a) => a,
+        Err(e @ AnswersError::Io(..)) => {
+            eprintln!("warning: {}", e);
+            Answers::default()
+        },
+        Err(e) => {
+            eprintln!("error: {}: {}", answers::DEFAULT_FILE, e);
+            process::exit(2);
+        }
+    }
+}
+
+// Runs the given day (or all days), returning true if no part failed.
+fn run(day: Option<u32>, options: &Options) -> bool {
+    let answers = load_answers();
+
+    let days: Vec<&Day> = match day {
+        Some(n) => {
+            let d = DAYS.iter().find(|d| d.day == n).unwrap_or_else(|| {
+                eprintln!("day {} is not registered (see 'aoc list')", n);
+                process::exit(2);
+            });
+
+            vec![d]
+        },
+        None => DAYS.iter().collect()
+    };
+
+    if options.memory {
+        memory::enable();
+    }
+
+    // Benchmarks would skew each other if run at the same time.
+    let threads = options.threads.unwrap_or_else(|| {
+        if options.bench.is_some() { 1 } else { thread::available_parallelism().map_or(1, |n| n.get()) }
+    });
+
+    let timestamp = perf::timestamp();
+    let start = Instant::now();
+
+    let mut reports = run_days(&days, threads, options, &answers);
+
+    let wall_time = start.elapsed().as_secs_f64();
+
+    if options.bench.is_some() {
+        aoc::bench::header();
+    }
+
+    let mut statuses = Vec::new();
+    let mut cpu_time = 0.0;
+    for r in &mut reports {
+        print!("{}", r.output);
+        statuses.append(&mut r.statuses);
+        cpu_time += r.time;
+    }
+
+    let passed = statuses.iter().filter(|s| **s == Status::Pass).count();
+    let failed = statuses.iter().filter(|s| matches!(s, Status::Fail(_))).count();
+    let unknown = statuses.iter().filter(|s| **s == Status::Unknown).count();
+    let errors = statuses.iter().filter(|s| matches!(s, Status::Failed(_))).count();
+    let timeouts = statuses.iter().filter(|s| **s == Status::Timeout).count();
+
+    println!();
+    println!("{} passed, {} failed, {} unknown, {} errors, {} timed out", passed, failed, unknown, errors, timeouts);
+    println!("{:.4}s wall clock, {:.4}s summed over days, {} thread(s)", wall_time, cpu_time, threads);
+
+    if !options.no_history {
+        record_history(timestamp, options.label.as_deref().unwrap_or(""), &reports);
+    }
+
+    failed == 0 && errors == 0 && timeouts == 0
+}
+
+// Adds the timings from a run to the performance history.
+// Failing to is only worth a warning, since the run itself succeeded.
+fn record_history(timestamp: u64, label: &str, reports: &[Report]) {
+    let revision = perf::revision();
+
+    let mut records = Vec::new();
+    for r in reports {
+        for &(part, seconds) in &r.timings {
+            records.push(Record { timestamp, revision: revision.clone(), label: label.to_string(), day: r.day, part, seconds });
+        }
+    }
+
+    if records.is_empty() {
+        return;
+    }
+
+    if let Err(e) = History::append(perf::DEFAULT_FILE, &records) {
+        eprintln!("warning: {}", e);
+    }
+}
+
+// Prints how the latest run's timings compare with the baseline's.
+// Returns true if no part got slower by more than the threshold.
+fn compare(baseline: Option<&str>, threshold: f64) -> bool {
+    let history = History::load(perf::DEFAULT_FILE).unwrap_or_else(|e| {
+        match e {
+            PerfError::Io(..) => eprintln!("error: {} (run some days first)", e),
+            _ => eprintln!("error: {}: {}", perf::DEFAULT_FILE, e)
+        }
+        process::exit(2);
+    });
+
+    let (latest, base) = history.latest_and_baseline(baseline).unwrap_or_else(|| {
+        match baseline {
+            Some(name) => eprintln!("error: no earlier run labelled or at revision '{}'", name),
+            None => eprintln!("error: need at least two runs to compare")
+        }
+        process::exit(2);
+    });
+
+    let describe = |r: &perf::Run| if r.label.is_empty() { r.revision.to_string() } else { format!("{} ({})", r.revision, r.label) };
+    println!("comparing {} with {}", describe(&latest), describe(&base));
+    println!();
+    println!("{:15} {:>10} {:>10} {:>9}", "", "before", "after", "change");
+
+    let mut slower = 0;
+    for c in perf::compare(&latest, &base) {
+        let s = format!("day{}:part{}", c.day, c.part);
+
+        match (c.before, c.percent()) {
+            (Some(before), Some(percent)) => {
+                if c.slower_than(threshold) {
+                    slower += 1;
+                    println!("{:15} {:>10.4} {:>10.4} {:>+8.1}%  SLOWER", s, before, c.after, percent);
+                }
+                else {
+                    println!("{:15} {:>10.4} {:>10.4} {:>+8.1}%", s, before, c.after, percent);
+                }
+            },
+            // Too fast in the baseline to compare against.
+            (Some(before), None) => println!("{:15} {:>10.4} {:>10.4} {:>9}", s, before, c.after, "n/a"),
+            _ => println!("{:15} {:>10} {:>10.4}", s, "-", c.after)
+        }
+    }
+
+    println!();
+    println!("{} part(s) more than {}% slower", slower, threshold);
+
+    slower == 0
+}
+
+// Downloads a day's input into the cache, returning false if that failed.
+fn fetch(day: u32) -> bool {
+    let config = Config::load().unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(2);
+    });
+
+    match fetch::fetch(&config, fetch::YEAR, day) {
+        Ok(Fetched::Cached(path)) => println!("day{}: already cached at {}", day, path.display()),
+        Ok(Fetched::Downloaded(path)) => println!("day{}: downloaded to {}", day, path.display()),
+        Err(e) => {
+            eprintln!("error: day{}: {}", day, e);
+            return false;
+        }
+    }
+
+    true
+}
+
+// Runs the days on a pool of worker threads, each taking the next day not yet started.
+// Returns the reports sorted by day, whatever order they finished in.
+fn run_days(days: &[&Day], threads: usize, options: &Options, answers: &Answers) -> Vec<Report> {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..threads.min(days.len()) {
+            let tx = tx.clone();
+            let next = &next;
+
+            scope.spawn(move || {
+                while let Some(d) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    // The receiver outlives the scope, so this can't fail.
+                    let _ = tx.send((d.day, run_day(d, options, answers)));
+                }
+            });
+        }
+    });
+
+    drop(tx);
+
+    let mut reports: Vec<(u32, Report)> = rx.into_iter().collect();
+    reports.sort_by_key(|(day, _)| *day);
+
+    reports.into_iter().map(|(_, r)| r).collect()
+}
+
+// Runs a day, treating a panic as a failure so that the remaining days still run.
+fn run_day(d: &Day, options: &Options, answers: &Answers) -> Report {
+    let start = Instant::now();
+
+    panic::catch_unwind(AssertUnwindSafe(|| (d.run)(options, answers))).unwrap_or_else(|e| {
+        let msg = aoc::panic_message(e.as_ref());
+
+        let output = format!("{:18} {:25} {:<20} {:6}  {}\n", format!("day{}", d.day), "", "FAILED", "", msg);
+        Report { day: d.day, output, statuses: vec![Status::Failed(msg)], time: start.elapsed().as_secs_f64(), ..Report::default() }
+    })
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match parse_args(&args) {
+        Ok(Command::List) => list(),
+        Ok(Command::Compare(baseline, threshold)) => {
+            if !compare(baseline.as_deref(), threshold) {
+                process::exit(1);
+            }
+        },
+        Ok(Command::Fetch(day)) => {
+            if !fetch(day) {
+                process::exit(1);
+            }
+        },
+        Ok(Command::Run(day, options)) => {
+            if !run(day, &options) {
+                process::exit(1);
+            }
+        },
+        Err(e) => {
+            eprintln!("error: {}\n{}", e, USAGE);
+            process::exit(2);
+        }
+    }
+}
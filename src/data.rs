@@ -1,70 +1,1181 @@
 // Helper methods for reading puzzle data.
 
-use std::str::FromStr;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
-use core::slice::Iter;
+use std::borrow::Cow;
+use std::convert::Infallible;
+use std::str::{FromStr, Lines};
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, prelude::*, BufReader};
+use std::iter::Enumerate;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
-pub fn get<T: FromStr>(file: &str) -> Vec<T> {
-    let mut input: Vec<T> = Vec::new();
+use crate::diagnostic::{Diagnose, Diagnostic};
+use crate::drawing::Grid;
+use crate::examples;
+use crate::fetch;
+use crate::error::Error;
 
-    // Read test data in, iterate over each line.
-    let f = File::open(file).unwrap_or_else(|_| panic!("Could not open {}", file));
-    let reader = BufReader::new(f);
+// Environment variable naming a directory of dayN.txt inputs to use instead of data/.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
 
-    for line in reader.lines() {
-        let line = line.unwrap_or_else(|_| panic!("Invalid line in {}", file));
+#[cfg(test)]
+mod test_source {
+    use super::*;
 
-        if let Ok(v) = T::from_str(line.trim()) {
-            input.push(v);
+    #[test]
+    fn from_arg() {
+        assert_eq!(Source::Stdin, Source::from_arg("-"));
+        assert_eq!(Source::File(PathBuf::from("other/day5.txt")), Source::from_arg("other/day5.txt"));
+    }
+
+    #[test]
+    fn resolve() {
+        let cached = Path::new("data/cache/2021/day5.txt");
+        assert_eq!(Source::Stdin, Source::resolve(5, Some("-"), Some("inputs"), Some(cached)));
+        assert_eq!(Source::from_arg("mine.txt"), Source::resolve(5, Some("mine.txt"), Some("inputs"), Some(cached)));
+        assert_eq!(Source::File(Path::new("inputs").join("day5.txt")), Source::resolve(5, None, Some("inputs"), Some(cached)));
+        assert_eq!(Source::File(cached.to_path_buf()), Source::resolve(5, None, None, Some(cached)));
+        assert_eq!(Source::default_for(5), Source::resolve(5, None, None, None));
+        assert_eq!(Source::from_arg("data/day5.txt"), Source::default_for(5));
+        assert_eq!(Source::from_arg("examples/day5/ex1.txt"), Source::example(5, "ex1"));
+    }
+}
+
+#[cfg(test)]
+mod test_lines {
+    use super::*;
+    use std::num::ParseIntError;
+
+    #[test]
+    fn all_good() {
+        let v: Vec<u32> = from_lines("1\n 2 \n3").expect("parsing failed!");
+        assert_eq!(vec![1, 2, 3], v);
+    }
+
+    #[test]
+    fn every_failure() {
+        let e = from_lines::<u32>("1\nx\n3\n\n-4").unwrap_err();
+
+        let lines: Vec<(usize, &str)> = e.errors.iter().map(|e| (e.line, e.text.as_str())).collect();
+        assert_eq!(vec![(2, "x"), (4, ""), (5, "-4")], lines);
+        assert_eq!("invalid digit found in string\n --> line 2, column 1\n  |\n2 | x\n  | ^", e.errors[0].to_string());
+    }
+
+    #[test]
+    fn diagnostics_underline_trimmed_line() {
+        let e = from_lines::<u32>("1\n  2x \n").unwrap_err();
+        let d = e.errors[0].diagnostic();
+        assert_eq!((2, 3..5, "  2x "), (d.line, d.span, d.text.as_str()));
+    }
+
+    #[test]
+    fn get_from_file() {
+        let v: Vec<u32> = get(Input::file("data/day1.txt")).expect("loading failed!");
+        assert_eq!(2000, v.len());
+
+        match get::<u32>(Input::file("data/day4.txt")) {
+            Err(LoadError::Parse(e)) => {
+                assert_eq!(Some("data/day4.txt"), e.file.as_deref());
+                assert_eq!(1, e.errors[0].line);
+            },
+            _ => panic!("expected a parse error!")
+        }
+
+        assert!(matches!(get::<u32>(Input::file("data/missing.txt")), Err(LoadError::Io(_, io::ErrorKind::NotFound))));
+    }
+
+    #[test]
+    fn get_from_text_and_reader() {
+        let v: Vec<u32> = get("1\n2\n").expect("loading failed!");
+        assert_eq!(vec![1, 2], v);
+
+        let v: Vec<u32> = get(Input::reader("3\n4\n".as_bytes())).expect("loading failed!");
+        assert_eq!(vec![3, 4], v);
+
+        // Lines are read as they're parsed, so invalid UTF-8 partway through is an I/O error.
+        assert!(matches!(get::<u32>(Input::reader(&b"1\n\xff\n"[..])), Err(LoadError::Io(_, io::ErrorKind::InvalidData))));
+
+        match get::<u32>("1\nx\n") {
+            Err(LoadError::Parse(e)) => assert_eq!("invalid digit found in string\n --> line 2, column 1\n  |\n2 | x\n  | ^", e.to_string()),
+            _ => panic!("expected a parse error!")
+        }
+    }
+
+    #[test]
+    fn same_results_from_every_input() {
+        let text = std::fs::read_to_string("data/day1.txt").expect("reading failed!");
+        let parse = |s: &str| s.parse::<u32>();
+
+        let from_file = get_with(Input::file("data/day1.txt"), &parse).expect("loading failed!");
+        let from_text = get_with(text.as_str(), &parse).expect("loading failed!");
+        let from_reader = get_with(Input::reader(text.as_bytes()), &parse).expect("loading failed!");
+
+        assert_eq!(from_file, from_text);
+        assert_eq!(from_file, from_reader);
+
+        let count = get_with_iter(text.as_str(), &mut |lines| lines.count()).expect("loading failed!");
+        assert_eq!(from_file.len(), count);
+
+        let last = get_with_iter(Input::file("data/day1.txt"), &mut |lines| lines.last().map(|(i, line)| (i, line.to_string())));
+        assert_eq!(Some((1999, text.lines().last().unwrap_or("").to_string())), last.expect("loading failed!"));
+    }
+
+    #[test]
+    fn get_with_errors() {
+        let parse = |s: &str| s.parse::<u32>();
+
+        match get_with(Input::file("data/missing.txt"), &parse) {
+            Err(LoadError::Io(file, io::ErrorKind::NotFound)) => assert_eq!("data/missing.txt", file),
+            _ => panic!("expected an I/O error!")
+        }
+
+        match get_with("1\nx\n3\ny", &parse) {
+            Err(LoadError::Parse(e)) => assert_eq!(vec![(2, "x"), (4, "y")], e.errors.iter().map(|e| (e.line, e.text.as_str())).collect::<Vec<_>>()),
+            _ => panic!("expected a parse error!")
+        }
+
+        assert!(matches!(get_with_iter(Input::file("data/missing.txt"), &mut |lines| lines.count()), Err(LoadError::Io(..))));
+    }
+
+    #[test]
+    fn display() {
+        let e = ParseErrors::<ParseIntError> { file: Some(String::from("in.txt")), errors: vec![
+            LineError { line: 2, text: String::from("x"), error: "x".parse::<u32>().unwrap_err() },
+            LineError { line: 4, text: String::from("yz"), error: "yz".parse::<u32>().unwrap_err() }
+        ]};
+
+        let expected = "\
+invalid digit found in string
+ --> in.txt:2:1
+  |
+2 | x
+  | ^
+invalid digit found in string
+ --> in.txt:4:1
+  |
+4 | yz
+  | ^^";
+        assert_eq!(expected, e.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_line_reader {
+    use super::*;
+
+    #[test]
+    fn borrowed() {
+        let mut lines = LineReader::new("a\r\nbb\n\nc".as_bytes());
+
+        assert_eq!((1, "a"), lines.next_line().unwrap().expect("read failed!"));
+        assert_eq!((2, "bb"), lines.next_line().unwrap().expect("read failed!"));
+        assert_eq!((3, ""), lines.next_line().unwrap().expect("read failed!"));
+        assert_eq!((4, "c"), lines.next_line().unwrap().expect("read failed!"));
+        assert!(lines.next_line().is_none());
+    }
+
+    #[test]
+    fn owned() {
+        let lines: Vec<(usize, String)> = LineReader::new("x\ny\n".as_bytes()).collect::<io::Result<_>>().expect("read failed!");
+        assert_eq!(vec![(1, String::from("x")), (2, String::from("y"))], lines);
+    }
+
+    #[test]
+    fn numbered() {
+        let s = "x\r\ny\n";
+        let lines: Vec<(usize, &str)> = numbered_lines(s).collect();
+        assert_eq!(vec![(1, "x"), (2, "y")], lines);
+    }
+}
+
+#[cfg(test)]
+mod test_grid {
+    use super::*;
+    use crate::drawing::Point;
+
+    #[test]
+    fn digits() {
+        let grid = digit_grid("123\n456\n").expect("parsing failed!");
+
+        assert_eq!((3, 2), (grid.xsize(), grid.ysize()));
+        assert_eq!(1, grid.get(&Point::new(0, 0)));
+        assert_eq!(6, grid.get(&Point::new(2, 1)));
+    }
+
+    #[test]
+    fn legend() {
+        let grid = char_grid("#.\n.#", &[('#', 1), ('.', 0)]).expect("parsing failed!");
+        assert_eq!(2, grid.count(&|v| v == 1));
+        assert_eq!(1, grid.get(&Point::new(1, 1)));
+    }
+
+    #[test]
+    fn errors() {
+        let short = Diagnostic::new("45", 3..3, "expected 3 cells, found 2").at_line(2);
+        assert_eq!(GridError::Ragged(short), digit_grid("123\n45\n").unwrap_err());
+
+        let long = Diagnostic::new(" 4567", 5..6, "expected 3 cells, found 4").at_line(2);
+        assert_eq!(GridError::Ragged(long), digit_grid("123\n 4567\n").unwrap_err());
+
+        let invalid = Diagnostic::new("4x6", 2..3, "unexpected 'x'").at_line(2);
+        assert_eq!(GridError::Invalid(invalid), digit_grid("123\n4x6\n").unwrap_err());
+
+        assert_eq!(GridError::Empty, digit_grid("\n").unwrap_err());
+    }
+
+    #[test]
+    fn errors_name_file() {
+        match load_digit_grid(Input::file("data/day1.txt")) {
+            Err(GridError::Ragged(d)) => assert_eq!(Some(String::from("data/day1.txt")), d.file),
+            r => panic!("unexpected result {:?}", r.map(|_| ()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_scan {
+    use super::*;
+    use std::ops::Range;
+
+    #[test]
+    fn captures() {
+        let c = scan_captures("{},{} -> {},{}", "0,9 -> 5,9").expect("matching failed!");
+        assert_eq!(vec![(1, "0"), (3, "9"), (8, "5"), (10, "9")], c);
+
+        let c = scan_captures("fold along {}={}", "fold along y=7").expect("matching failed!");
+        assert_eq!(vec![(12, "y"), (14, "7")], c);
+    }
+
+    #[test]
+    fn typed() {
+        let (x1, y1, x2, y2): (i64, i64, u32, u32) = scan("{},{} -> {},{}", "0,9 -> 5,9").expect("scanning failed!");
+        assert_eq!((0, 9, 5, 9), (x1, y1, x2, y2));
+
+        let (dir, n): (String, u32) = scan("{} {}", "forward 5").expect("scanning failed!");
+        assert_eq!(("forward", 5), (dir.as_str(), n));
+    }
+
+    fn error(pattern: &str, s: &str) -> (Range<usize>, String) {
+        let e = scan::<(char, i64)>(pattern, s).unwrap_err();
+        assert_eq!(s, e.text);
+        (e.span, e.message)
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!((8..9, String::from("expected 'fold along '")), error("fold along {}={}", "fold alung y=7"));
+        assert_eq!((13..13, String::from("expected '='")), error("fold along {}={}", "fold along y"));
+        assert_eq!((3..5, String::from("'xy': invalid digit found in string")), error("{},{}", "3,xy"));
+        assert_eq!((6..7, String::from("expected '>' at end of line")), error("<{},{}>", "<3,4>!"));
+        assert_eq!((3..5, String::from("expected end of line")), error("ab", "abcd"));
+
+        let e = scan::<(i64,)>("{},{}", "3,4").unwrap_err();
+        assert_eq!("pattern has 2 captures, expected 1\n --> line 1, column 1\n  |\n1 | 3,4\n  | ^^^", e.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_sections {
+    use super::*;
+
+    #[test]
+    fn split() {
+        let s = "a\nb\n\n\nc\n  \nd\ne\n";
+        let sections = split_sections(s);
+
+        assert_eq!(3, sections.len());
+        assert_eq!(Section { index: 1, line: 1, text: "a\nb" }, sections[0]);
+        assert_eq!(Section { index: 2, line: 5, text: "c" }, sections[1]);
+        assert_eq!(Section { index: 3, line: 7, text: "d\ne" }, sections[2]);
+    }
+
+    #[test]
+    fn split_crlf() {
+        let sections = split_sections("a\r\n\r\nb\r\n");
+        assert_eq!(vec!["a", "b"], sections.iter().map(|s| s.text).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn typed() {
+        let (template, rules): (String, Vec<u32>) = sections("NNCB\n\n1\n2\n").expect("parsing failed!");
+        assert_eq!("NNCB", template);
+        assert_eq!(vec![1, 2], rules);
+    }
+
+    #[test]
+    fn errors() {
+        let e = sections::<(String, Vec<u32>)>("NNCB\n\n1\nx\n").unwrap_err();
+        let e = e.to_string();
+        assert!(e.starts_with("section 2: invalid digit found in string\n --> line 4, column 1\n"), "{}", e);
+
+        let e = sections::<(String, Vec<u32>)>("NNCB\n").unwrap_err();
+        assert_eq!("expected 2 sections, found 1", e.to_string());
+    }
+
+    #[test]
+    fn closure() {
+        let s = split_sections("1,2,3\n\n4");
+        let calls: Vec<u32> = s[0].parse_with(|t| from_separated(t, ',')).expect("parsing failed!");
+        assert_eq!(vec![1, 2, 3], calls);
+
+        let e = s[1].parse_with(|t| t.parse::<bool>()).unwrap_err();
+        assert_eq!("section 2: provided string was not `true` or `false`", e.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test_tokens {
+    use super::*;
+
+    #[test]
+    fn whitespace() {
+        let t = Tokenizer::whitespace();
+        assert_eq!(vec![(2, "22"), (7, "13"), (11, "17")], t.tokens(" 22   13  17 ").expect("tokenizing failed!"));
+        assert_eq!(vec![1, 4, 90], from_separated::<u32>("\t1  4 90", t).expect("parsing failed!"));
+    }
+
+    #[test]
+    fn chars() {
+        assert_eq!(vec![1, 2, 3], from_separated::<u32>("1,2,3", ',').expect("parsing failed!"));
+        assert_eq!(vec![1, 2, 3], from_separated::<u32>("1, 2;  3", &[',', ';']).expect("parsing failed!"));
+
+        let t = Tokenizer::from(',');
+        assert_eq!(vec![(1, "a"), (3, ""), (4, "b")], t.tokens("a,,b").expect("tokenizing failed!"));
+        assert_eq!(vec![(1, "a"), (4, "b")], t.empty(Empty::Skip).tokens("a,,b").expect("tokenizing failed!"));
+        assert_eq!(vec![(1, " a"), (4, " b ")], t.trim(false).tokens(" a, b ").expect("tokenizing failed!"));
+    }
+
+    #[test]
+    fn strs() {
+        let t = Tokenizer::from(&[" -> ", "->", " | "]);
+        assert_eq!(vec![(1, "a"), (6, "b"), (9, "c"), (13, "d")], t.tokens("a -> b->c | d").expect("tokenizing failed!"));
+    }
+
+    #[test]
+    fn errors() {
+        let e = from_separated::<u32>("1, 2,x3", ',').unwrap_err();
+        assert_eq!(6..8, e.span);
+        assert_eq!("'x3': invalid digit found in string", e.message);
+
+        let e = Tokenizer::from(',').empty(Empty::Reject).tokens("1, ,3").unwrap_err();
+        assert_eq!(4..4, e.span);
+        assert_eq!("expected a value", e.message);
+    }
+
+    #[test]
+    fn integers_anywhere() {
+        assert_eq!(vec![20, 30, -10, -5], integers("target area: x=20..30, y=-10..-5"));
+        assert_eq!(vec![1, 3], integers("1-3 a: abcde"));
+        assert_eq!(vec![-7, 42], integers("--7 and 42."));
+        assert!(integers("no numbers - here").is_empty());
+    }
+}
+
+// Where puzzle input is read from.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Source {
+    File(PathBuf),
+    Stdin
+}
+
+impl Source {
+    // A path given on the command line, where "-" means stdin.
+    pub fn from_arg(s: &str) -> Source {
+        if s == "-" { Source::Stdin } else { Source::File(PathBuf::from(s)) }
+    }
+
+    // The committed input for a day.
+    pub fn default_for(day: u32) -> Source {
+        Source::File(PathBuf::from(format!("data/day{}.txt", day)))
+    }
+
+    // One of a day's example inputs, by name.
+    pub fn example(day: u32, name: &str) -> Source {
+        Source::File(examples::day_dir(examples::DIR, day).join(format!("{}.txt", name)))
+    }
+
+    // Resolves the input for a day, in order from: the given path (or "-" for stdin),
+    // a dayN.txt in the given input directory, the input fetched into the cache
+    // (if there is one), and the committed input.
+    pub fn resolve(day: u32, input: Option<&str>, input_dir: Option<&str>, cached: Option<&Path>) -> Source {
+        match (input, input_dir, cached) {
+            (Some(i), _, _) => Source::from_arg(i),
+            (None, Some(dir), _) => Source::File(Path::new(dir).join(format!("day{}.txt", day))),
+            (None, None, Some(file)) => Source::File(file.to_path_buf()),
+            (None, None, None) => Source::default_for(day)
+        }
+    }
+
+    // As resolve, taking the input directory from AOC_INPUT_DIR and looking
+    // for the day's input where aoc fetch would have cached it.
+    pub fn for_day(day: u32, input: Option<&str>) -> Source {
+        let dir = env::var(INPUT_DIR_VAR).ok();
+        let cached = fetch::Config::load().ok().map(|config| config.cache_file(fetch::YEAR, day)).filter(|file| file.is_file());
+        Source::resolve(day, input, dir.as_deref(), cached.as_deref())
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Source::File(path) => fs::read_to_string(path),
+            Source::Stdin => io::read_to_string(io::stdin())
         }
     }
 
-    input
+    pub fn reader(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            Source::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+            Source::Stdin => Ok(Box::new(BufReader::new(io::stdin())))
+        }
+    }
 }
 
-// Returns a vector of the objects constructed from the given function for each line of a file.
-pub fn get_with<T: FromStr>(file: &str, func: &dyn Fn(&str) -> Result<T, T::Err>) -> Result<Vec<T>, T::Err> {
-    let mut input: Vec<T> = Vec::new();
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "<stdin>")
+        }
+    }
+}
 
-    // Read test data in, iterate over each line.
-    let f = File::open(file).unwrap_or_else(|_| panic!("Could not open {}", file));
-    let reader = BufReader::new(f);
+// What a loader reads from: the input text itself, a file or stdin, or any reader.
+// A &str converts to Text, so tests can pass example text straight to the same loaders
+// the days use; a path must be given as Input::file.
+pub enum Input<'a> {
+    Text(&'a str),
+    Source(Source),
+    Reader(Box<dyn Read + 'a>)
+}
 
-    for line in reader.lines() {
-        let line = line.unwrap_or_else(|_| panic!("Invalid line in {}", file));
+impl<'a> Input<'a> {
+    // A path, where "-" means stdin.
+    pub fn file(path: &str) -> Input<'a> {
+        Input::Source(Source::from_arg(path))
+    }
+
+    pub fn reader(r: impl Read + 'a) -> Input<'a> {
+        Input::Reader(Box::new(r))
+    }
 
-        let n = func(line.trim())?;
+    // The file the input comes from, for error messages.
+    pub fn name(&self) -> Option<String> {
+        match self {
+            Input::Source(source) => Some(source.to_string()),
+            _ => None
+        }
+    }
 
-        input.push(n);
+    // The whole input, borrowed if it was given as text.
+    pub fn text(self) -> io::Result<Cow<'a, str>> {
+        match self {
+            Input::Text(s) => Ok(Cow::Borrowed(s)),
+            Input::Source(source) => source.read().map(Cow::Owned),
+            Input::Reader(r) => io::read_to_string(r).map(Cow::Owned)
+        }
     }
 
-    Ok(input)
+    // The input a line at a time.
+    pub fn lines(self) -> io::Result<LineReader<Box<dyn BufRead + 'a>>> {
+        let reader: Box<dyn BufRead + 'a> = match self {
+            Input::Text(s) => Box::new(s.as_bytes()),
+            Input::Source(source) => source.reader()?,
+            Input::Reader(r) => Box::new(BufReader::new(r))
+        };
+
+        Ok(LineReader::new(reader))
+    }
 }
 
-pub fn get_with_iter<T>(file: &str, f_iter: &mut dyn Fn(&mut Iter<&str>) -> T) -> T {
-    let mut input = Vec::new();
+impl<'a> From<&'a str> for Input<'a> {
+    fn from(s: &'a str) -> Input<'a> {
+        Input::Text(s)
+    }
+}
 
-    // Read test data in, iterate over each line.
-    let f = File::open(file).unwrap_or_else(|_| panic!("Could not open {}", file));
-    let reader = BufReader::new(f);
+impl<'a> From<&'a String> for Input<'a> {
+    fn from(s: &'a String) -> Input<'a> {
+        Input::Text(s)
+    }
+}
+
+impl From<Source> for Input<'_> {
+    fn from(source: Source) -> Self {
+        Input::Source(source)
+    }
+}
+
+// Reads an input for a loader, naming it in the error if it fails.
+fn read_input<'a>(input: impl Into<Input<'a>>) -> Result<(Cow<'a, str>, Option<String>), (String, io::ErrorKind)> {
+    let input = input.into();
+    let name = input.name();
+
+    match input.text() {
+        Ok(s) => Ok((s, name)),
+        Err(e) => Err((name.unwrap_or_else(|| String::from("<input>")), e.kind()))
+    }
+}
 
-    for line in reader.lines() {
-        let line = line.unwrap_or_else(|_| panic!("Invalid line in {}", file));
+// Lines read lazily from a reader, numbered from 1, without their line endings.
+// next_line lends each line from one reused buffer, so reading allocates nothing
+// per line; iterating instead yields owned copies.
+pub struct LineReader<R> {
+    reader: R,
+    buf: String,
+    line: usize
+}
 
-        input.push(line);
+impl<R: BufRead> LineReader<R> {
+    pub fn new(reader: R) -> LineReader<R> {
+        LineReader { reader, buf: String::new(), line: 0 }
     }
 
-    let input: Vec<&str> = input.iter().map(|s| s as &str).collect();
+    pub fn next_line(&mut self) -> Option<io::Result<(usize, &str)>> {
+        self.buf.clear();
+
+        match self.reader.read_line(&mut self.buf) {
+            Ok(0) => None,
+            Ok(_) => {
+                self.line += 1;
+                Some(Ok((self.line, self.buf.trim_end_matches(['\r', '\n']))))
+            },
+            Err(e) => Some(Err(e))
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for LineReader<R> {
+    type Item = io::Result<(usize, String)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_line().map(|r| r.map(|(n, line)| (n, line.to_string())))
+    }
+}
+
+// Numbered lines borrowed from input that has already been read in one go.
+pub fn numbered_lines(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    s.lines().enumerate().map(|(i, line)| (i + 1, line))
+}
+
+// A line of input that failed to parse.
+#[derive(Debug)]
+pub struct LineError<E> {
+    // Numbered from 1.
+    pub line: usize,
+    pub text: String,
+    pub error: E
+}
+
+impl<E: Diagnose> LineError<E> {
+    pub fn diagnostic(&self) -> Diagnostic {
+        self.error.diagnose(&self.text).at_line(self.line)
+    }
+}
+
+impl<E: Diagnose> fmt::Display for LineError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.diagnostic())
+    }
+}
+
+// Every line of an input that failed to parse, and the file they came from if known.
+#[derive(Debug)]
+pub struct ParseErrors<E> {
+    pub file: Option<String>,
+    pub errors: Vec<LineError<E>>
+}
+
+impl<E: Diagnose> ParseErrors<E> {
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.errors.iter().map(|e| {
+            let d = e.diagnostic();
+            match &self.file {
+                Some(file) => d.in_file(file),
+                None => d
+            }
+        }).collect()
+    }
+}
+
+impl<E: Diagnose> fmt::Display for ParseErrors<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let errors: Vec<String> = self.diagnostics().iter().map(|d| d.to_string()).collect();
+        write!(f, "{}", errors.join("\n"))
+    }
+}
+
+impl<E: Diagnose + fmt::Debug> error::Error for ParseErrors<E> {}
+
+#[derive(Debug)]
+pub enum LoadError<E> {
+    Io(String, io::ErrorKind),
+    Parse(ParseErrors<E>)
+}
+
+impl<E: Diagnose> fmt::Display for LoadError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io(file, kind) => write!(f, "could not read {}: {:?}", file, kind),
+            LoadError::Parse(e) => write!(f, "{}", e)
+        }
+    }
+}
+
+impl<E: Diagnose + fmt::Debug> error::Error for LoadError<E> {}
+
+// Parses every (trimmed) line of an input, reading it a line at a time.
+// Fails with every line that doesn't parse, rather than skipping them.
+pub fn get<'a, T: FromStr>(input: impl Into<Input<'a>>) -> Result<Vec<T>, LoadError<T::Err>> {
+    load_lines(input.into(), &T::from_str)
+}
 
-    return f_iter(&mut input.iter());
+// As get, but each (trimmed) line is parsed by func.
+pub fn get_with<'a, T: FromStr>(input: impl Into<Input<'a>>, func: &dyn Fn(&str) -> Result<T, T::Err>) -> Result<Vec<T>, LoadError<T::Err>> {
+    load_lines(input.into(), func)
 }
 
-pub fn from_separated<T: FromStr>(s: &str, sep: char) -> Result<Vec<T>, T::Err> {
+fn load_lines<T, E>(input: Input, func: &dyn Fn(&str) -> Result<T, E>) -> Result<Vec<T>, LoadError<E>> {
+    let file = input.name();
+    let io_error = |e: io::Error| LoadError::Io(file.clone().unwrap_or_else(|| String::from("<input>")), e.kind());
+
+    let mut lines = input.lines().map_err(io_error)?;
     let mut v = Vec::new();
-    for i in s.split(sep) {
-        v.push(T::from_str(i)?)
+    let mut errors = Vec::new();
+
+    while let Some(line) = lines.next_line() {
+        let (n, line) = line.map_err(io_error)?;
+        match func(line.trim()) {
+            Ok(t) => v.push(t),
+            Err(error) => errors.push(LineError { line: n, text: line.to_string(), error })
+        }
+    }
+
+    if errors.is_empty() { Ok(v) } else { Err(LoadError::Parse(ParseErrors { file, errors })) }
+}
+
+// Reads an input in one go, then hands its lines to f_iter, each with its
+// index from 0. The lines are borrowed from the input rather than copied.
+pub fn get_with_iter<'a, T>(input: impl Into<Input<'a>>, f_iter: &mut dyn Fn(&mut Enumerate<Lines>) -> T) -> Result<T, LoadError<Infallible>> {
+    let (s, _) = read_input(input).map_err(|(file, kind)| LoadError::Io(file, kind))?;
+
+    Ok(f_iter(&mut s.lines().enumerate()))
+}
+
+// What tokens are separated by.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Separator<'a> {
+    // Runs of whitespace, so there are never empty tokens.
+    Whitespace,
+
+    Char(char),
+
+    // Any one of these characters.
+    Chars(&'a [char]),
+
+    // Any one of these strings, the longest first where more than one match.
+    Strs(&'a [&'a str])
+}
+
+// What to do with an empty token, such as between two separators in a row.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Empty {
+    Keep,
+    Skip,
+    Reject
+}
+
+// How to split a line into tokens, e.g. Tokenizer::new(Separator::Chars(&[',', ';'])).
+// Tokens are trimmed of surrounding whitespace unless trim is turned off.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Tokenizer<'a> {
+    pub separator: Separator<'a>,
+    pub trim: bool,
+    pub empty: Empty
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn new(separator: Separator<'a>) -> Tokenizer<'a> {
+        Tokenizer { separator, trim: true, empty: Empty::Keep }
+    }
+
+    pub fn whitespace() -> Tokenizer<'a> {
+        Tokenizer::new(Separator::Whitespace)
+    }
+
+    pub fn trim(self, trim: bool) -> Tokenizer<'a> {
+        Tokenizer { trim, ..self }
     }
 
-    Ok(v)
+    pub fn empty(self, empty: Empty) -> Tokenizer<'a> {
+        Tokenizer { empty, ..self }
+    }
+
+    // Splits s into tokens, each with the column it starts at (numbered from 1).
+    pub fn tokens<'s>(&self, s: &'s str) -> Result<Vec<(usize, &'s str)>, Diagnostic> {
+        // Byte ranges of each token before trimming.
+        let mut ranges = Vec::new();
+        match self.separator {
+            Separator::Whitespace => {
+                let offset = |t: &str| t.as_ptr() as usize - s.as_ptr() as usize;
+                ranges.extend(s.split_whitespace().map(|t| offset(t)..offset(t) + t.len()));
+            },
+            Separator::Char(c) => split_chars(s, &[c], &mut ranges),
+            Separator::Chars(chars) => split_chars(s, chars, &mut ranges),
+            Separator::Strs(strs) => {
+                let (mut start, mut pos) = (0, 0);
+                while pos < s.len() {
+                    match strs.iter().filter(|sep| !sep.is_empty() && s[pos..].starts_with(**sep)).map(|sep| sep.len()).max() {
+                        Some(n) => {
+                            ranges.push(start..pos);
+                            pos += n;
+                            start = pos;
+                        },
+                        None => pos += s[pos..].chars().next().map_or(1, char::len_utf8)
+                    }
+                }
+                ranges.push(start..s.len());
+            }
+        }
+
+        let column = |pos: usize| s[..pos].chars().count() + 1;
+        let mut tokens = Vec::new();
+
+        for range in ranges {
+            let raw = &s[range.clone()];
+            let (start, token) = if self.trim { (range.start + raw.len() - raw.trim_start().len(), raw.trim()) } else { (range.start, raw) };
+
+            if token.is_empty() {
+                match self.empty {
+                    Empty::Keep => (),
+                    Empty::Skip => continue,
+                    Empty::Reject => return Err(Diagnostic::new(s, column(start)..column(start), "expected a value"))
+                }
+            }
+
+            tokens.push((column(start), token));
+        }
+
+        Ok(tokens)
+    }
+
+    // Splits s into tokens and parses each one, pointing at the first that doesn't parse.
+    pub fn parse<T: FromStr>(&self, s: &str) -> Result<Vec<T>, Diagnostic> where T::Err: fmt::Display {
+        let mut v = Vec::new();
+        for (column, token) in self.tokens(s)? {
+            let span = column..column + token.chars().count();
+            v.push(T::from_str(token).map_err(|e| Diagnostic::new(s, span, format!("'{}': {}", token, e)))?);
+        }
+
+        Ok(v)
+    }
+}
+
+fn split_chars(s: &str, chars: &[char], ranges: &mut Vec<Range<usize>>) {
+    let mut start = 0;
+    for (i, c) in s.char_indices().filter(|(_, c)| chars.contains(c)) {
+        ranges.push(start..i);
+        start = i + c.len_utf8();
+    }
+    ranges.push(start..s.len());
+}
+
+impl From<char> for Tokenizer<'static> {
+    fn from(c: char) -> Tokenizer<'static> {
+        Tokenizer::new(Separator::Char(c))
+    }
+}
+
+impl<'a> From<&'a [char]> for Tokenizer<'a> {
+    fn from(chars: &'a [char]) -> Tokenizer<'a> {
+        Tokenizer::new(Separator::Chars(chars))
+    }
+}
+
+impl<'a, const N: usize> From<&'a [char; N]> for Tokenizer<'a> {
+    fn from(chars: &'a [char; N]) -> Tokenizer<'a> {
+        Tokenizer::new(Separator::Chars(chars))
+    }
+}
+
+impl<'a> From<&'a [&'a str]> for Tokenizer<'a> {
+    fn from(strs: &'a [&'a str]) -> Tokenizer<'a> {
+        Tokenizer::new(Separator::Strs(strs))
+    }
+}
+
+impl<'a, const N: usize> From<&'a [&'a str; N]> for Tokenizer<'a> {
+    fn from(strs: &'a [&'a str; N]) -> Tokenizer<'a> {
+        Tokenizer::new(Separator::Strs(strs))
+    }
+}
+
+// Parses each token of s, split by a separator character, a slice of them (or of
+// strings), or a Tokenizer, e.g. from_separated::<u32>(" 1  4 90", Tokenizer::whitespace()).
+pub fn from_separated<'a, T: FromStr>(s: &str, sep: impl Into<Tokenizer<'a>>) -> Result<Vec<T>, Diagnostic> where T::Err: fmt::Display {
+    sep.into().parse(s)
+}
+
+// Every signed integer in s, whatever else is around them, e.g. [-3, 5] from "x=-3..5".
+// A '-' is only a sign if it's right before a digit and not right after one,
+// so ranges such as "1-3" give [1, 3].
+pub fn integers(s: &str) -> Vec<i64> {
+    let bytes = s.as_bytes();
+    let mut v = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let negative = bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) && (i == 0 || !bytes[i - 1].is_ascii_digit());
+        if !negative && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        // Saturates rather than failing on a number too big for an i64.
+        v.push(s[start..i].parse().unwrap_or(if negative { i64::MIN } else { i64::MAX }));
+    }
+
+    v
+}
+
+// Parses each (trimmed) line of a string, failing with every line that does not parse.
+pub fn from_lines<T: FromStr>(s: &str) -> Result<Vec<T>, ParseErrors<T::Err>> {
+    parse_lines(s, &T::from_str)
+}
+
+fn parse_lines<T, E>(s: &str, func: &dyn Fn(&str) -> Result<T, E>) -> Result<Vec<T>, ParseErrors<E>> {
+    let mut v = Vec::new();
+    let mut errors = Vec::new();
+
+    for (n, line) in numbered_lines(s) {
+        match func(line.trim()) {
+            Ok(t) => v.push(t),
+            Err(error) => errors.push(LineError { line: n, text: line.to_string(), error })
+        }
+    }
+
+    if errors.is_empty() { Ok(v) } else { Err(ParseErrors { file: None, errors }) }
+}
+
+// A block of input separated from the others by blank lines.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Section<'a> {
+    // Numbered from 1.
+    pub index: usize,
+
+    // The line the section starts on, numbered from 1.
+    pub line: usize,
+
+    pub text: &'a str
+}
+
+impl Section<'_> {
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+
+    pub fn parse<T: FromSection>(&self) -> Result<T, SectionError<T::Err>> {
+        T::from_section(self).map_err(|error| SectionError { index: self.index, error })
+    }
+
+    // Parses the section's text with a closure rather than a FromSection type.
+    pub fn parse_with<T, E>(&self, f: impl FnOnce(&str) -> Result<T, E>) -> Result<T, SectionError<E>> {
+        f(self.text).map_err(|error| SectionError { index: self.index, error })
+    }
+}
+
+// A section that failed to parse.
+#[derive(Debug)]
+pub struct SectionError<E> {
+    pub index: usize,
+    pub error: E
+}
+
+impl<E: fmt::Display> fmt::Display for SectionError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "section {}: {}", self.index, self.error)
+    }
+}
+
+impl<E: fmt::Display + fmt::Debug> error::Error for SectionError<E> {}
+
+// Splits input into sections on blank lines. Runs of blank lines count as one separator.
+pub fn split_sections(s: &str) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+
+    // Byte offset and line number of the start of the current section, if in one.
+    let mut start: Option<(usize, usize)> = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for (i, line) in s.split_inclusive('\n').enumerate() {
+        if line.trim().is_empty() {
+            if let Some((from, first)) = start.take() {
+                sections.push(Section { index: sections.len() + 1, line: first, text: &s[from..end] });
+            }
+        }
+        else {
+            start.get_or_insert((offset, i + 1));
+            end = offset + line.trim_end_matches(['\r', '\n']).len();
+        }
+
+        offset += line.len();
+    }
+
+    if let Some((from, first)) = start {
+        sections.push(Section { index: sections.len() + 1, line: first, text: &s[from..end] });
+    }
+
+    sections
+}
+
+// Something that can be parsed from one section of input.
+pub trait FromSection: Sized {
+    type Err;
+
+    fn from_section(section: &Section) -> Result<Self, Self::Err>;
+}
+
+// The whole section, trimmed.
+impl FromSection for String {
+    type Err = Infallible;
+
+    fn from_section(section: &Section) -> Result<String, Self::Err> {
+        Ok(section.text.trim().to_string())
+    }
+}
+
+// One value per line, with errors numbered by their line in the whole input.
+impl<T: FromStr> FromSection for Vec<T> {
+    type Err = ParseErrors<T::Err>;
+
+    fn from_section(section: &Section) -> Result<Vec<T>, Self::Err> {
+        from_lines(section.text).map_err(|mut e| {
+            for error in &mut e.errors {
+                error.line += section.line - 1;
+            }
+            e
+        })
+    }
+}
+
+// A fixed number of sections, each parsed as its own type.
+pub trait FromSections: Sized {
+    fn from_sections(sections: &[Section]) -> Result<Self, Error>;
+}
+
+macro_rules! from_sections_tuple {
+    ($n:expr; $($t:ident $i:tt),+) => {
+        impl<$($t: FromSection),+> FromSections for ($($t,)+) where $($t::Err: fmt::Display),+ {
+            fn from_sections(sections: &[Section]) -> Result<Self, Error> {
+                if sections.len() != $n {
+                    return Err(Error::msg(format!("expected {} sections, found {}", $n, sections.len())));
+                }
+
+                Ok(($(sections[$i].parse::<$t>().map_err(Error::msg)?,)+))
+            }
+        }
+    }
+}
+
+from_sections_tuple!(1; A 0);
+from_sections_tuple!(2; A 0, B 1);
+from_sections_tuple!(3; A 0, B 1, C 2);
+
+// Splits input into sections and parses each as the matching type in T,
+// for example data::sections::<(String, Vec<Rule>)>(input).
+pub fn sections<T: FromSections>(s: &str) -> Result<T, Error> {
+    T::from_sections(&split_sections(s))
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum GridError {
+    Io(String, io::ErrorKind),
+    Empty,
+
+    // A row that isn't as long as the first.
+    Ragged(Diagnostic),
+
+    // A character with no value.
+    Invalid(Diagnostic)
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::Io(file, kind) => write!(f, "could not read {}: {:?}", file, kind),
+            GridError::Empty => write!(f, "grid has no rows"),
+            GridError::Ragged(d) | GridError::Invalid(d) => write!(f, "{}", d)
+        }
+    }
+}
+
+impl GridError {
+    fn in_file(self, file: Option<String>) -> GridError {
+        match (self, file) {
+            (GridError::Ragged(d), Some(file)) => GridError::Ragged(d.in_file(file)),
+            (GridError::Invalid(d), Some(file)) => GridError::Invalid(d.in_file(file)),
+            (e, _) => e
+        }
+    }
+}
+
+impl error::Error for GridError {}
+
+// Builds a grid from rows of characters, each mapped to a value by f.
+// Every row must be as long as the first; trailing blank lines are ignored.
+pub fn grid_with<T: Clone + Default>(s: &str, f: impl Fn(char) -> Option<T>) -> Result<Grid<T>, GridError> {
+    let mut rows: Vec<Vec<T>> = Vec::new();
+
+    for (n, text) in numbered_lines(s.trim_end()) {
+        let line = text.trim();
+
+        // Columns are found in the trimmed line, then moved along to match the original.
+        let diagnostic = |span, message| Diagnostic::new(line, span, message).diagnose(text).at_line(n);
+
+        let mut row = Vec::with_capacity(line.len());
+        for (i, c) in line.chars().enumerate() {
+            row.push(f(c).ok_or_else(|| GridError::Invalid(diagnostic(i + 1..i + 2, format!("unexpected '{}'", c))))?);
+        }
+
+        if let Some(first) = rows.first() {
+            if row.len() != first.len() {
+                // Point at the extra cells, or where the missing ones should be.
+                let span = first.len().min(row.len()) + 1..row.len() + 1;
+                return Err(GridError::Ragged(diagnostic(span, format!("expected {} cells, found {}", first.len(), row.len()))));
+            }
+        }
+        rows.push(row);
+    }
+
+    if rows.is_empty() || rows[0].is_empty() {
+        return Err(GridError::Empty);
+    }
+
+    Ok(Grid::from_array(rows))
+}
+
+// A grid of single decimal digits.
+pub fn digit_grid(s: &str) -> Result<Grid, GridError> {
+    grid_with(s, |c| c.to_digit(10))
+}
+
+// A grid of characters mapped to values by a legend, e.g. &[('#', true), ('.', false)].
+pub fn char_grid<T: Clone + Default>(s: &str, legend: &[(char, T)]) -> Result<Grid<T>, GridError> {
+    grid_with(s, |c| legend.iter().find(|(l, _)| *l == c).map(|(_, v)| v.clone()))
+}
+
+// As digit_grid, from any input.
+pub fn load_digit_grid<'a>(input: impl Into<Input<'a>>) -> Result<Grid, GridError> {
+    let (s, file) = read_input(input).map_err(|(file, kind)| GridError::Io(file, kind))?;
+    digit_grid(&s).map_err(|e| e.in_file(file))
+}
+
+// As char_grid, from any input.
+pub fn load_char_grid<'a, T: Clone + Default>(input: impl Into<Input<'a>>, legend: &[(char, T)]) -> Result<Grid<T>, GridError> {
+    let (s, file) = read_input(input).map_err(|(file, kind)| GridError::Io(file, kind))?;
+    char_grid(&s, legend).map_err(|e| e.in_file(file))
+}
+
+// Matches s against a pattern such as "{},{} -> {},{}", where each {} captures
+// everything up to the literal text that follows it. Returns each capture with
+// the column it starts at, or a diagnostic pointing at where s stops matching.
+pub fn scan_captures<'a>(pattern: &str, s: &'a str) -> Result<Vec<(usize, &'a str)>, Diagnostic> {
+    let column = |pos: usize| s[..pos].chars().count() + 1;
+    let at = |pos: usize, message: String| {
+        let c = column(pos);
+        Diagnostic::new(s, c..(c + 1).min(s.chars().count() + 1), message)
+    };
+
+    // Points at the first character from pos that differs from the expected literal.
+    let mismatch = |pos: usize, literal: &str| {
+        let same: usize = s[pos..].chars().zip(literal.chars()).take_while(|(a, b)| a == b).map(|(a, _)| a.len_utf8()).sum();
+        at(pos + same, format!("expected '{}'", literal))
+    };
+
+    let literals: Vec<&str> = pattern.split("{}").collect();
+    let (first, rest) = (literals[0], &literals[1..]);
+
+    if !s.starts_with(first) {
+        return Err(mismatch(0, first));
+    }
+    let mut pos = first.len();
+    let mut captures = Vec::new();
+
+    for (i, literal) in rest.iter().enumerate() {
+        // The last capture runs to whatever the pattern ends with.
+        let end = if i == rest.len() - 1 {
+            if !s[pos..].ends_with(literal) {
+                let message = format!("expected '{}' at end of line", literal);
+                // Point at where the literal would have to start.
+                let n = literal.chars().count();
+                let start = s[pos..].char_indices().rev().nth(n - 1).map_or(pos, |(i, _)| pos + i);
+                return Err(at(start, message));
+            }
+            s.len() - literal.len()
+        }
+        else {
+            match s[pos..].find(literal) {
+                Some(found) => pos + found,
+                None => {
+                    // Point at where the literal seems to start, or else the end of the line.
+                    let start = literal.chars().next().and_then(|c| s[pos..].find(c)).map_or(s.len(), |p| pos + p);
+                    return Err(mismatch(start, literal));
+                }
+            }
+        };
+
+        captures.push((column(pos), &s[pos..end]));
+        pos = end + literal.len();
+    }
+
+    if rest.is_empty() && pos < s.len() {
+        return Err(Diagnostic::new(s, column(pos)..column(s.len()), "expected end of line"));
+    }
+
+    Ok(captures)
+}
+
+// A tuple of values that can each be parsed from one capture.
+pub trait FromCaptures: Sized {
+    // Captures are from scan_captures on the line s.
+    fn from_captures(s: &str, captures: &[(usize, &str)]) -> Result<Self, Diagnostic>;
+}
+
+macro_rules! from_captures_tuple {
+    ($n:expr; $($t:ident $i:tt),+) => {
+        impl<$($t: FromStr),+> FromCaptures for ($($t,)+) where $($t::Err: fmt::Display),+ {
+            fn from_captures(s: &str, captures: &[(usize, &str)]) -> Result<Self, Diagnostic> {
+                if captures.len() != $n {
+                    return Err(Diagnostic::whole_line(s, format!("pattern has {} captures, expected {}", captures.len(), $n)));
+                }
+
+                Ok(($({
+                    let (column, text) = captures[$i];
+                    let span = column..column + text.chars().count();
+                    $t::from_str(text).map_err(|e| Diagnostic::new(s, span, format!("'{}': {}", text, e)))?
+                },)+))
+            }
+        }
+    }
+}
+
+from_captures_tuple!(1; A 0);
+from_captures_tuple!(2; A 0, B 1);
+from_captures_tuple!(3; A 0, B 1, C 2);
+from_captures_tuple!(4; A 0, B 1, C 2, D 3);
+from_captures_tuple!(5; A 0, B 1, C 2, D 3, E 4);
+from_captures_tuple!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+
+// Matches s against a pattern and parses each capture as the matching type in T,
+// for example data::scan::<(i64, i64)>("{},{}", "3,4").
+pub fn scan<T: FromCaptures>(pattern: &str, s: &str) -> Result<T, Diagnostic> {
+    T::from_captures(s, &scan_captures(pattern, s)?)
 }
@@ -0,0 +1,148 @@
+// Errors that point at where in the input they went wrong. They're shown
+// rustc-style, with the offending line and the part of it at fault underlined:
+//
+//     y coordinate is not a number: invalid digit found in string
+//      --> data/day13.txt:3:4
+//       |
+//     3 | 10,x4
+//       |    ^^
+
+use std::char::ParseCharError;
+use std::convert::Infallible;
+use std::error;
+use std::fmt::{self, Display};
+use std::num::{ParseFloatError, ParseIntError};
+use std::ops::Range;
+use std::str::ParseBoolError;
+
+use crate::error::Error;
+
+#[cfg(test)]
+mod test_diagnostic {
+    use super::*;
+
+    #[test]
+    fn underlines_span() {
+        let d = Diagnostic::new("10,x4", 4..6, "y coordinate is not a number").at_line(3).in_file("data/day13.txt");
+        assert_eq!("y coordinate is not a number\n --> data/day13.txt:3:4\n  |\n3 | 10,x4\n  |    ^^", d.to_string());
+    }
+
+    #[test]
+    fn without_file() {
+        let d = Diagnostic::new("abc", 4..4, "expected ','").at_line(12);
+        assert_eq!("expected ','\n  --> line 12, column 4\n   |\n12 | abc\n   |    ^", d.to_string());
+    }
+
+    #[test]
+    fn keeps_tabs_aligned() {
+        let d = Diagnostic::new("\tab", 3..4, "bad").at_line(1);
+        assert!(d.to_string().ends_with("1 | \tab\n  | \t ^"));
+    }
+
+    #[test]
+    fn diagnose_whole_line() {
+        let e = "x".parse::<u32>().unwrap_err();
+        let d = e.diagnose("  xy ");
+        assert_eq!(3..5, d.span);
+        assert_eq!("invalid digit found in string", d.message);
+    }
+
+    #[test]
+    fn diagnose_trimmed_line() {
+        // The span is into the trimmed line that was parsed.
+        let d = Diagnostic::new("1,x", 3..4, "not a number").diagnose("  1,x");
+        assert_eq!(5..6, d.span);
+        assert_eq!("  1,x", d.text);
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Diagnostic {
+    pub file: Option<String>,
+
+    // Numbered from 1.
+    pub line: usize,
+
+    // The columns at fault, numbered from 1. An empty span points between two
+    // characters, such as at the end of the line.
+    pub span: Range<usize>,
+
+    // The whole of the offending line.
+    pub text: String,
+    pub message: String
+}
+
+impl Diagnostic {
+    // A diagnostic for the first (or only) line of an input.
+    pub fn new<M: Display>(text: &str, span: Range<usize>, message: M) -> Diagnostic {
+        Diagnostic { file: None, line: 1, span, text: text.to_string(), message: message.to_string() }
+    }
+
+    // A diagnostic about everything on a line but its surrounding whitespace.
+    pub fn whole_line<M: Display>(text: &str, message: M) -> Diagnostic {
+        let start = text.chars().count() - text.trim_start().chars().count() + 1;
+        Diagnostic::new(text, start..start + text.trim().chars().count(), message)
+    }
+
+    pub fn at_line(mut self, line: usize) -> Diagnostic {
+        self.line = line;
+        self
+    }
+
+    pub fn in_file<F: Display>(mut self, file: F) -> Diagnostic {
+        self.file = Some(file.to_string());
+        self
+    }
+
+    pub fn column(&self) -> usize {
+        self.span.start
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.message)?;
+
+        let gutter = " ".repeat(self.line.to_string().len());
+        match &self.file {
+            Some(file) => writeln!(f, "{}--> {}:{}:{}", gutter, file, self.line, self.column())?,
+            None => writeln!(f, "{}--> line {}, column {}", gutter, self.line, self.column())?
+        }
+
+        // Tabs are kept so the underline lines up however they're shown.
+        let indent: String = self.text.chars().chain(std::iter::repeat(' ')).take(self.span.start.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' }).collect();
+        let carets = "^".repeat(self.span.len().max(1));
+
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(f, "{} | {}{}", gutter, indent, carets)
+    }
+}
+
+impl error::Error for Diagnostic {}
+
+// An error from parsing a line, that can be shown as a diagnostic pointing into
+// that line. Unless the error knows better, the whole line is underlined.
+pub trait Diagnose: Display {
+    fn diagnose(&self, line: &str) -> Diagnostic {
+        Diagnostic::whole_line(line, self)
+    }
+}
+
+// The line given is the one before trimming, so the span moves along with it.
+impl Diagnose for Diagnostic {
+    fn diagnose(&self, line: &str) -> Diagnostic {
+        let offset = line.chars().count() - line.trim_start().chars().count();
+        let span = self.span.start + offset..self.span.end + offset;
+
+        Diagnostic { span, text: line.to_string(), ..self.clone() }
+    }
+}
+
+impl Diagnose for Error {}
+impl Diagnose for Infallible {}
+impl Diagnose for ParseIntError {}
+impl Diagnose for ParseFloatError {}
+impl Diagnose for ParseBoolError {}
+impl Diagnose for ParseCharError {}
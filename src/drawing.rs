@@ -1,8 +1,15 @@
 // Implementations relating to drawing.
 
+use std::fmt::{self, Display};
 use std::hash::Hash;
+use std::ops::{Add, Range};
+use std::slice::Iter;
 use std::str::FromStr;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::error;
+use std::io::{self, Write};
+
+use crate::diagnostic::Diagnostic;
 
 #[cfg(test)]
 mod test_parse_point {
@@ -31,14 +38,263 @@ mod test_parse_point {
         assert_eq!(9, p.x);
         assert_eq!(3, p.y);
     }
+
+    #[test]
+    fn errors() {
+        let e = Point::from_str("12").unwrap_err();
+        assert_eq!((3..3, "missing ',' and y coordinate"), (e.span, e.message.as_str()));
+
+        let e = Point::from_str("1x,5").unwrap_err();
+        assert_eq!((1..3, "x coordinate is not a number: invalid digit found in string"), (e.span, e.message.as_str()));
+
+        let e = Point::from_str("10,").unwrap_err();
+        assert_eq!((4..4, "y coordinate is not a number: cannot parse integer from empty string"), (e.span, e.message.as_str()));
+    }
 }
 
-#[derive(Debug)]
-pub enum PointParseError {
-    MissingX,
-    MissingY,
-    NotNumberX,
-    NotNumberY
+#[cfg(test)]
+mod test_grid {
+    use super::*;
+
+    fn grid() -> Grid {
+        Grid::from_array(vec![vec![1, 2, 3], vec![4, 5, 6]])
+    }
+
+    #[test]
+    fn points_row_by_row() {
+        let points = vec![Point::new(0, 0), Point::new(1, 0), Point::new(2, 0), Point::new(0, 1), Point::new(1, 1), Point::new(2, 1)];
+        assert_eq!(points, grid().points());
+        assert_eq!(points, grid().iter_points().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn neighbours_inside() {
+        let g = grid();
+        assert_eq!(vec![Point::new(1, 0), Point::new(0, 1)], g.neighbours(&Point::new(0, 0)));
+        assert_eq!(5, g.neighbours_diagonal(&Point::new(1, 0)).len());
+        assert_eq!(5, g.iter_neighbours_diagonal(&Point::new(1, 0)).count());
+        assert_eq!(0, g.iter_neighbours(&Point::new(5, 5)).count());
+    }
+
+    #[test]
+    fn only_points_set() {
+        let mut g = Grid::new(3, 3);
+        g.set(&Point::new(1, 1), 5);
+        g.set(&Point::new(1, 0), 0);
+
+        assert_eq!(vec![Point::new(1, 0), Point::new(1, 1)], g.points());
+        assert_eq!(vec![Point::new(1, 0)], g.neighbours(&Point::new(1, 1)));
+        assert_eq!(1, g.count(&|v| v == 0));
+        assert!(g.contains(&Point::new(1, 0)) && !g.contains(&Point::new(0, 0)));
+        assert_eq!(None, g.cell(&Point::new(0, 0)));
+        assert_eq!(0, g.get(&Point::new(0, 0)));
+
+        g.do_each(&|v| v + 1);
+        assert_eq!((1, 6, 0), (g.get(&Point::new(1, 0)), g.get(&Point::new(1, 1)), g.get(&Point::new(2, 2))));
+    }
+
+    #[test]
+    fn set_grows() {
+        let mut g = grid();
+        g.set(&Point::new(4, 2), 9);
+        assert_eq!((5, 3), (g.xsize(), g.ysize()));
+        assert_eq!(6, g.get(&Point::new(2, 1)));
+        assert_eq!(9, g.get(&Point::new(4, 2)));
+        assert_eq!(0, g.get(&Point::new(3, 0)));
+        assert_eq!(0, g.get(&Point::new(-1, 0)));
+    }
+
+    #[test]
+    fn negative() {
+        let mut g = grid();
+        g.set(&Point::new(-1, -2), 7);
+        g.increment(&Point::new(-1, 0));
+        assert_eq!((3, 2), (g.xsize(), g.ysize()));
+        assert_eq!((7, 1, 5), (g.get(&Point::new(-1, -2)), g.get(&Point::new(-1, 0)), g.get(&Point::new(1, 1))));
+        assert_eq!(Point::new(-1, -2), g.points()[0]);
+        assert_eq!(vec![Point::new(-1, 0), Point::new(1, 0), Point::new(0, 1)], g.neighbours(&Point::new(0, 0)));
+    }
+
+    #[test]
+    fn paste() {
+        let mut g = grid();
+        g.paste(&grid(), &Point::new(3, 0));
+        assert_eq!(Grid::from_array(vec![vec![1, 2, 3, 1, 2, 3], vec![4, 5, 6, 4, 5, 6]]), g);
+
+        let mut g = grid();
+        g.paste(&grid(), &Point::new(0, -2));
+        assert_eq!((3, 2, 12), (g.xsize(), g.ysize(), g.points().len()));
+        assert_eq!(4, g.get(&Point::new(0, -1)));
+
+        let moved = grid().move_to(&Point::new(1, 1));
+        assert_eq!((4, 3), (moved.xsize(), moved.ysize()));
+        assert_eq!(vec![Point::new(1, 1), Point::new(2, 1), Point::new(3, 1), Point::new(1, 2), Point::new(2, 2), Point::new(3, 2)], moved.points());
+        assert_eq!(1, moved.get(&Point::new(1, 1)));
+    }
+
+    #[test]
+    fn any_cell_type() {
+        #[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+        enum Tile { #[default] Open, Wall }
+
+        let mut g: Grid<Tile> = Grid::new(2, 2);
+        g.set(&Point::new(1, 0), Tile::Wall);
+        g.set(&Point::new(0, 1), Tile::Open);
+        assert_eq!(Tile::Wall, g.get(&Point::new(1, 0)));
+        assert_eq!(Tile::Open, g.get(&Point::new(5, 5)));
+        assert_eq!(1, g.count(&|t| t == Tile::Open));
+
+        let mut g = Grid::from_array(vec![vec!['a', 'b']]);
+        g.do_each(&|c| c.to_ascii_uppercase());
+        assert_eq!(Some(&'B'), g.cell(&Point::new(1, 0)));
+        assert_eq!(None, g.cell(&Point::new(2, 0)));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("123\n456", grid().to_string());
+    }
+
+    #[test]
+    fn render_options() {
+        let g = Grid::from_array(vec![vec![0, 0, 0], vec![0, 1, 0], vec![0, 1, 1]]);
+        let draw = |v: &u32| if *v == 1 { Some('#') } else { None };
+
+        assert_eq!("...\n.#.\n.##", g.render(draw, &RenderOptions::default()));
+        assert_eq!("   \n # \n ##", g.render(draw, &RenderOptions { empty: ' ', ..RenderOptions::default() }));
+        assert_eq!("#.\n##", g.render(draw, &RenderOptions { crop: true, ..RenderOptions::default() }));
+        assert_eq!(" 12\n1#.\n2##", g.render(draw, &RenderOptions { crop: true, axes: true, ..RenderOptions::default() }));
+    }
+
+    #[test]
+    fn render_wide_axes() {
+        let mut g = SparseGrid::new();
+        g.set(&Point::new(9, -1), true);
+        g.set(&Point::new(10, 0), true);
+
+        let options = RenderOptions { crop: true, axes: true, ..RenderOptions::default() };
+        assert_eq!("   1\n  90\n-1#.\n 0.#", g.render(|&dot| if dot { Some('#') } else { None }, &options));
+    }
+
+    #[test]
+    fn sparse() {
+        let mut g = SparseGrid::from_points(&[Point::new(3, 1), Point::new(-2, 0)], 1);
+        g.increment(&Point::new(3, 1));
+        assert_eq!(vec![Point::new(-2, 0), Point::new(3, 1)], g.points());
+        assert_eq!(2, g.get(&Point::new(3, 1)));
+        assert_eq!((4, 2), (g.xsize(), g.ysize()));
+        assert_eq!(1, g.count(&|v| v == 1));
+    }
+}
+
+#[cfg(test)]
+mod test_ocr {
+    use super::*;
+
+    // The lit points of a picture drawn with '#'.
+    fn dots(picture: &str) -> Vec<Point> {
+        picture.lines().enumerate()
+            .flat_map(|(y, row)| row.chars().enumerate().filter(|(_, c)| *c == '#').map(move |(x, _)| Point::new(x as i64, y as i64)))
+            .collect()
+    }
+
+    #[test]
+    fn small() {
+        let picture = "\
+.##....##.####..##.
+#..#....#....#.#..#
+#.......#...#..#...
+#.##....#..#...#.##
+#..#.#..#.#....#..#
+.###..##..####..###";
+        assert_eq!(Ok(String::from("GJZG")), ocr(&dots(picture)));
+
+        // Where the letters are and how far apart doesn't matter.
+        let moved: Vec<Point> = dots("###...#...#\n#..#..#...#\n#..#...#.#.\n###.....#..\n#.#.....#..\n#..#....#..").iter().map(|p| Point::new(p.x + 5, p.y - 3)).collect();
+        assert_eq!(Ok(String::from("RY")), ocr(&moved));
+    }
+
+    #[test]
+    fn large() {
+        let picture = "\
+#....#..######
+##...#..#.....
+##...#..#.....
+#.#..#..#.....
+#.#..#..#####.
+#..#.#..#.....
+#..#.#..#.....
+#...##..#.....
+#...##..#.....
+#....#..######";
+        assert_eq!(Ok(String::from("NE")), ocr(&dots(picture)));
+    }
+
+    #[test]
+    fn grids() {
+        let grid = Grid::from_array(vec![vec![1, 1, 1, 1], vec![1, 0, 0, 0], vec![1, 1, 1, 0], vec![1, 0, 0, 0], vec![1, 0, 0, 0], vec![1, 0, 0, 0]]);
+        assert_eq!(Ok(String::from("F")), grid.letters(|&v| v == 1));
+
+        let sparse = SparseGrid::from_points(&dots("#...\n#...\n#...\n#...\n#...\n####"), true);
+        assert_eq!(Ok(String::from("L")), sparse.letters(|&dot| dot));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Err(OcrError::Empty), ocr(&[]));
+        assert_eq!(Err(OcrError::Height(3)), ocr(&dots("#\n.\n#")));
+        assert_eq!(Err(OcrError::Unknown(String::from("##\n#.\n.#\n#.\n.#\n##"))), ocr(&dots("##\n#.\n.#\n#.\n.#\n##")));
+    }
+}
+
+#[cfg(test)]
+mod test_images {
+    use super::*;
+
+    fn image(write: impl Fn(&mut Vec<u8>) -> io::Result<()>) -> String {
+        let mut out = Vec::new();
+        write(&mut out).expect("writing failed!");
+        String::from_utf8(out).expect("not utf-8!")
+    }
+
+    #[test]
+    fn pgm() {
+        let grid = Grid::from_array(vec![vec![0, 9], vec![3, 0]]);
+        let pgm = image(|w| grid.write_pgm(w, |&v| (v * 255 / 9) as u8));
+        assert_eq!("P2\n2 2\n255\n0 255\n85 0\n", pgm);
+    }
+
+    #[test]
+    fn ppm() {
+        let grid = Grid::from_array(vec![vec![true, false]]);
+        let ppm = image(|w| grid.write_ppm(w, |&wall| if wall { Colour(0, 0, 255) } else { Colour::WHITE }));
+        assert_eq!("P3\n2 1\n255\n0 0 255 255 255 255\n", ppm);
+    }
+
+    #[test]
+    fn svg() {
+        let grid = Grid::from_array(vec![vec![1, 0], vec![0, 2]]);
+        let palette = |&v: &u32| if v == 0 { None } else { Some(Colour::grey(v as u8 * 100)) };
+        let svg = image(|w| grid.write_svg(w, palette, Some(&[Point::new(0, 0), Point::new(1, 1)])));
+
+        let lines: Vec<&str> = svg.lines().collect();
+        assert!(lines[0].starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 2 2" width="20" height="20""#), "{}", lines[0]);
+        assert_eq!(r##"<rect x="0" y="0" width="1" height="1" fill="#646464"/>"##, lines[1]);
+        assert_eq!(r##"<rect x="1" y="1" width="1" height="1" fill="#c8c8c8"/>"##, lines[2]);
+        assert!(lines[3].starts_with(r#"<polyline points="0.5,0.5 1.5,1.5""#), "{}", lines[3]);
+        assert_eq!("</svg>", lines[4]);
+
+        let svg = image(|w| grid.write_svg(w, palette, None));
+        assert!(!svg.contains("polyline"));
+    }
+
+    #[test]
+    fn sparse_to_grid() {
+        let sparse = SparseGrid::from_points(&[Point::new(2, 1), Point::new(-1, 0)], 7);
+        let grid = sparse.to_grid();
+        assert_eq!(vec![Point::new(-1, 0), Point::new(2, 1)], grid.points());
+        assert_eq!((3, 2, 7), (grid.xsize(), grid.ysize(), grid.get(&Point::new(-1, 0))));
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
@@ -47,25 +303,19 @@ pub struct Point {
     pub x: i64
 }
 
+// Parses "x,y", pointing at whichever coordinate is wrong if that fails.
 impl FromStr for Point {
-    type Err = PointParseError;
+    type Err = Diagnostic;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut s_iter = s.split(',');
+        let end = s.chars().count() + 1;
+        let (x, y) = s.split_once(',').ok_or_else(|| Diagnostic::new(s, end..end, "missing ',' and y coordinate"))?;
 
-        // We expect two numbers, x and y.
-        let x = s_iter.next().ok_or(PointParseError::MissingX)?;
-        let y = s_iter.next().ok_or(PointParseError::MissingY)?;
+        let x_span = 1..x.chars().count() + 1;
+        let y_span = x_span.end + 1..end;
 
-        let x: i64 = match x.parse() {
-            Ok(n) => n,
-            Result::Err(_) => return Result::Err(PointParseError::NotNumberX)
-        };
-
-        let y: i64 = match y.parse() {
-            Ok(n) => n,
-            Result::Err(_) => return Result::Err(PointParseError::NotNumberY)
-        };
+        let x = x.parse().map_err(|e| Diagnostic::new(s, x_span, format!("x coordinate is not a number: {}", e)))?;
+        let y = y.parse().map_err(|e| Diagnostic::new(s, y_span, format!("y coordinate is not a number: {}", e)))?;
 
         Ok(Point { x, y })
     }
@@ -82,45 +332,150 @@ impl Point {
     }
 }
 
-#[derive(Clone)]
-pub struct Grid {
+// How render draws a grid as text.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RenderOptions {
+    // Drawn for cells with no character of their own.
+    pub empty: char,
+
+    // Numbers the columns across the top and the rows down the left.
+    pub axes: bool,
+
+    // Leaves out the empty rows and columns around the edges.
+    pub crop: bool
+}
+
+impl Default for RenderOptions {
+    fn default() -> RenderOptions {
+        RenderOptions { empty: '.', axes: false, crop: false }
+    }
+}
+
+// The size of an SVG's cells when it's first shown, before any zooming.
+const SVG_SCALE: i64 = 10;
+
+// A colour for image export, as red, green and blue.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Colour(pub u8, pub u8, pub u8);
+
+impl Colour {
+    pub const BLACK: Colour = Colour(0, 0, 0);
+    pub const WHITE: Colour = Colour(255, 255, 255);
+
+    // A shade of grey, from black at 0 to white at 255.
+    pub fn grey(shade: u8) -> Colour {
+        Colour(shade, shade, shade)
+    }
+}
+
+// As an SVG or HTML colour, e.g. "#ff8000".
+impl Display for Colour {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+// Draws the points in xs by ys a row to a line. Column numbers are written
+// downwards, so each takes up a single character's width:
+//
+//        1
+//      890
+//     0#..
+//     1.##
+fn render(xs: Range<i64>, ys: Range<i64>, draw: impl Fn(Point) -> Option<char>, options: &RenderOptions) -> String {
+    let (xs, ys) = if options.crop {
+        let drawn: Vec<Point> = ys.flat_map(|y| xs.clone().map(move |x| Point::new(x, y))).filter(|p| draw(*p).is_some()).collect();
+        let xs = drawn.iter().map(|p| p.x).min().unwrap_or(0)..drawn.iter().map(|p| p.x + 1).max().unwrap_or(0);
+        let ys = drawn.iter().map(|p| p.y).min().unwrap_or(0)..drawn.iter().map(|p| p.y + 1).max().unwrap_or(0);
+        (xs, ys)
+    }
+    else {
+        (xs, ys)
+    };
+
+    let mut lines = Vec::new();
+    let margin = if options.axes { ys.clone().map(|y| y.to_string().len()).max().unwrap_or(0) } else { 0 };
+
+    if options.axes {
+        let labels: Vec<String> = xs.clone().map(|x| x.to_string()).collect();
+        let height = labels.iter().map(String::len).max().unwrap_or(0);
+
+        for i in 0..height {
+            let digits: String = labels.iter().map(|l| (l.len() + i).checked_sub(height).and_then(|j| l.chars().nth(j)).unwrap_or(' ')).collect();
+            lines.push(format!("{:margin$}{}", "", digits, margin = margin).trim_end().to_string());
+        }
+    }
+
+    for y in ys {
+        let label = if options.axes { format!("{:>margin$}", y, margin = margin) } else { String::new() };
+        let row: String = xs.clone().map(|x| draw(Point::new(x, y)).unwrap_or(options.empty)).collect();
+        lines.push(label + &row);
+    }
+
+    lines.join("\n")
+}
+
+// Neighbours in the order they're given: left, up, right, down, then the diagonals.
+const NEIGHBOURS: [(i64, i64); 8] = [(-1, 0), (0, -1), (1, 0), (0, 1), (-1, -1), (-1, 1), (1, -1), (1, 1)];
+
+// A grid of values, kept row by row in one block of cells covering every point
+// that's been set, so getting and setting a point is just indexing. Setting a
+// point outside the block grows it, even to negative coordinates. Points that
+// haven't been set have no value: get gives the default (0 for numbers) for
+// them, and points, neighbours, do_each and count leave them out. xsize and
+// ysize are one more than the largest x and y that have been set.
+//
+// points and neighbours return a Vec, so the grid can be changed while going
+// through them. iter_points and iter_neighbours go through the same points
+// without allocating.
+#[derive(Clone, Debug)]
+pub struct Grid<T = u32> {
     xsize: i64,
     ysize: i64,
-    grid: HashMap<Point, u32>
+
+    // The block of cells, width by height from (xmin, ymin).
+    xmin: i64,
+    ymin: i64,
+    width: i64,
+    height: i64,
+    cells: Vec<Option<T>>
 }
 
-impl Grid {
-    pub fn new(xsize: i64, ysize: i64) -> Grid {
-        Grid { xsize, ysize, grid: HashMap::new() }
+impl<T: Clone + Default> Grid<T> {
+    pub fn new(xsize: i64, ysize: i64) -> Grid<T> {
+        let (width, height) = (xsize.max(0), ysize.max(0));
+        Grid { xsize, ysize, xmin: 0, ymin: 0, width, height, cells: vec![None; (width * height) as usize] }
     }
 
-    pub fn from_array(grid: Vec<Vec<u32>>) -> Grid {
-        let xsize = grid[0].len() as i64;
+    pub fn from_array(grid: Vec<Vec<T>>) -> Grid<T> {
+        let xsize = grid.first().map_or(0, |row| row.len()) as i64;
         let ysize = grid.len() as i64;
 
-        let mut g = HashMap::new();
-        for (y, row) in grid.into_iter().enumerate() {
-            for (x, v) in row.into_iter().enumerate() {
-                g.insert(Point::new(x as i64, y as i64), v);
-            }
-        }
-
-        Grid { xsize, ysize, grid: g }
+        Grid { xsize, ysize, xmin: 0, ymin: 0, width: xsize, height: ysize, cells: grid.into_iter().flatten().map(Some).collect() }
     }
 
-    pub fn from_points(points: &Vec<Point>, value: u32) -> Grid {
-        // Hacky, need to sort out proper grid sizing later.
-        let mut grid = Grid { grid: HashMap::new(), xsize: 0, ysize: 0 };
+    // A grid with just the points set, to value.
+    pub fn from_points(points: &[Point], value: T) -> Grid<T> {
+        let mut grid = Grid::new(0, 0);
+        if let (Some(xmin), Some(ymin)) = (points.iter().map(|p| p.x).min(), points.iter().map(|p| p.y).min()) {
+            let xmax = points.iter().map(|p| p.x + 1).max().unwrap_or(0);
+            let ymax = points.iter().map(|p| p.y + 1).max().unwrap_or(0);
+            grid.fit(xmin..xmax, ymin..ymax);
+        }
+
         for p in points {
-            grid.set(p, value);
+            grid.set(p, value.clone());
         }
         grid
     }
 
+    // The points that have been set, row by row.
     pub fn points(&self) -> Vec<Point> {
-        let mut p: Vec<Point> = self.grid.keys().copied().collect();
-        p.sort();
-        p
+        self.iter_points().collect()
+    }
+
+    pub fn iter_points(&self) -> Points<'_, T> {
+        Points { grid: self, next: 0 }
     }
 
     pub fn xsize(&self) -> i64 {
@@ -135,92 +490,456 @@ impl Grid {
         self.xsize * self.ysize
     }
 
-    pub fn neighbours(&self, p: &Point) -> Vec<Point> {
-        let x = p.x;
-        let y = p.y;
-        let neighbours = vec![
-            Point::new(x-1, y),
-            Point::new(x, y-1),
-            Point::new(x+1, y),
-            Point::new(x, y+1),
-        ];
+    // Whether p has been set.
+    pub fn contains(&self, p: &Point) -> bool {
+        self.cell(p).is_some()
+    }
+
+    fn index(&self, p: &Point) -> Option<usize> {
+        if (self.xmin..self.xmin + self.width).contains(&p.x) && (self.ymin..self.ymin + self.height).contains(&p.y) {
+            Some(((p.y - self.ymin) * self.width + p.x - self.xmin) as usize)
+        }
+        else {
+            None
+        }
+    }
+
+    // Each point that has been set, with its value.
+    fn values(&self) -> impl Iterator<Item = (Point, &T)> {
+        let (xmin, ymin, width) = (self.xmin, self.ymin, self.width);
+        self.cells.iter().enumerate().filter_map(move |(i, v)| {
+            v.as_ref().map(|v| (Point::new(xmin + i as i64 % width, ymin + i as i64 / width), v))
+        })
+    }
 
-        return neighbours.iter().filter(|p| self.grid.contains_key(p)).copied().collect();
+    // The neighbours of p that have been set: left, up, right and down.
+    pub fn neighbours(&self, p: &Point) -> Vec<Point> {
+        self.iter_neighbours(p).collect()
     }
 
+    // As neighbours, then the diagonals.
     pub fn neighbours_diagonal(&self, p: &Point) -> Vec<Point> {
-        let x = p.x;
-        let y = p.y;
-        let mut neighbours = self.neighbours(p);
+        self.iter_neighbours_diagonal(p).collect()
+    }
 
-        neighbours.push(Point::new(x-1, y-1));
-        neighbours.push(Point::new(x-1, y+1));
-        neighbours.push(Point::new(x+1, y-1));
-        neighbours.push(Point::new(x+1, y+1));
+    pub fn iter_neighbours(&self, p: &Point) -> Neighbours<'_, T> {
+        Neighbours { grid: self, centre: *p, offsets: NEIGHBOURS[..4].iter() }
+    }
 
-        return neighbours.iter().filter(|p| self.grid.contains_key(p)).copied().collect();
+    pub fn iter_neighbours_diagonal(&self, p: &Point) -> Neighbours<'_, T> {
+        Neighbours { grid: self, centre: *p, offsets: NEIGHBOURS.iter() }
+    }
+
+    // A copy of the grid with each point moved along by p.
+    pub fn move_to(&self, p: &Point) -> Grid<T> {
+        Grid {
+            xsize: p.x + self.xsize,
+            ysize: p.y + self.ysize,
+            xmin: p.x + self.xmin,
+            ymin: p.y + self.ymin,
+            ..self.clone()
+        }
     }
 
-    pub fn move_to(&self, p: &Point) -> Grid {
-        let mut new_points = HashMap::new();
+    // Sets each point that has been set in other, moved along by p, to its
+    // value in other.
+    pub fn paste(&mut self, other: &Grid<T>, p: &Point) {
+        self.fit(p.x + other.xmin..p.x + other.xmin + other.width, p.y + other.ymin..p.y + other.ymin + other.height);
+
+        for (q, v) in other.values() {
+            self.set(&Point::new(p.x + q.x, p.y + q.y), v.clone());
+        }
+
+        self.xsize = self.xsize.max(p.x + other.xsize);
+        self.ysize = self.ysize.max(p.y + other.ysize);
+    }
 
-        for point in self.points() {
-            let new_point = Point::new(point.x + p.x, point.y + p.y);
-            new_points.insert(new_point, self.get(&point));
+    // Grows the block of cells to cover xs by ys, keeping what's already in it.
+    fn fit(&mut self, xs: Range<i64>, ys: Range<i64>) {
+        if xs.is_empty() || ys.is_empty() || (self.index(&Point::new(xs.start, ys.start)).is_some() && self.index(&Point::new(xs.end - 1, ys.end - 1)).is_some()) {
+            return;
         }
 
-        let new_xsize = p.x + self.xsize as i64;
-        let new_ysize = p.y + self.ysize as i64;
+        let xmin = xs.start.min(self.xmin);
+        let ymin = ys.start.min(self.ymin);
+        let width = xs.end.max(self.xmin + self.width) - xmin;
+        let height = ys.end.max(self.ymin + self.height) - ymin;
 
-        return Grid {
-            xsize: new_xsize,
-            ysize: new_ysize,
-            grid: new_points
+        let mut cells = vec![None; (width * height) as usize];
+        for (y, row) in self.cells.chunks(self.width.max(1) as usize).enumerate() {
+            let start = ((self.ymin + y as i64 - ymin) * width + self.xmin - xmin) as usize;
+            cells[start..start + row.len()].clone_from_slice(row);
         }
+
+        *self = Grid { xsize: self.xsize, ysize: self.ysize, xmin, ymin, width, height, cells };
     }
 
-    pub fn set(&mut self, p: &Point, val: u32) {
+    pub fn set(&mut self, p: &Point, val: T) {
+        self.fit(p.x..p.x + 1, p.y..p.y + 1);
+
         if p.x >= self.xsize { self.xsize = p.x + 1 }
         if p.y >= self.ysize { self.ysize = p.y + 1 }
-        if !self.grid.contains_key(p) {
-            self.grid.insert(*p, val);
+
+        let i = self.index(p).unwrap();
+        self.cells[i] = Some(val);
+    }
+
+    // The value at p, or the default if it hasn't been set.
+    pub fn get(&self, p: &Point) -> T {
+        self.cell(p).cloned().unwrap_or_default()
+    }
+
+    // The value at p, if it has been set.
+    pub fn cell(&self, p: &Point) -> Option<&T> {
+        self.index(p).and_then(|i| self.cells[i].as_ref())
+    }
+
+    pub fn do_each(&mut self, f: &dyn Fn(T) -> T) {
+        for v in self.cells.iter_mut().flatten() {
+            *v = f(v.clone());
         }
-        else
-        {
-            *self.grid.get_mut(p).unwrap() = val;
+    }
+
+    // Counts the number of points set for which the predicate is true.
+    pub fn count(&self, f: &dyn Fn(T) -> bool) -> u32 {
+        self.cells.iter().flatten().filter(|v| f((*v).clone())).count() as u32
+    }
+
+    // Draws the grid as text, with a character for each cell given by draw.
+    // Points that haven't been set are empty.
+    pub fn render(&self, draw: impl Fn(&T) -> Option<char>, options: &RenderOptions) -> String {
+        let xmin = self.values().map(|(p, _)| p.x).min().unwrap_or(0).min(0);
+        let ymin = self.values().map(|(p, _)| p.y).min().unwrap_or(0).min(0);
+
+        render(xmin..self.xsize, ymin..self.ysize, |p| self.cell(&p).and_then(&draw), options)
+    }
+
+    // Reads the letters spelled out by the cells that are lit.
+    pub fn letters(&self, lit: impl Fn(&T) -> bool) -> Result<String, OcrError> {
+        ocr(&self.values().filter(|(_, v)| lit(v)).map(|(p, _)| p).collect::<Vec<_>>())
+    }
+
+    // Writes the grid from (0, 0) to (xsize, ysize) as a plain (text) PGM
+    // image, a pixel per cell, with each cell's shade of grey given by shade,
+    // from black at 0 to white at 255.
+    pub fn write_pgm(&self, w: &mut impl Write, shade: impl Fn(&T) -> u8) -> io::Result<()> {
+        writeln!(w, "P2\n{} {}\n255", self.xsize, self.ysize)?;
+        self.write_pixels(w, |v| shade(v).to_string())
+    }
+
+    // As write_pgm, as a plain PPM image in colour.
+    pub fn write_ppm(&self, w: &mut impl Write, palette: impl Fn(&T) -> Colour) -> io::Result<()> {
+        writeln!(w, "P3\n{} {}\n255", self.xsize, self.ysize)?;
+        self.write_pixels(w, |v| {
+            let Colour(r, g, b) = palette(v);
+            format!("{} {} {}", r, g, b)
+        })
+    }
+
+    fn write_pixels(&self, w: &mut impl Write, pixel: impl Fn(&T) -> String) -> io::Result<()> {
+        for y in 0..self.ysize {
+            let row: Vec<String> = (0..self.xsize).map(|x| pixel(&self.get(&Point::new(x, y)))).collect();
+            writeln!(w, "{}", row.join(" "))?;
         }
+
+        Ok(())
     }
 
+    // Writes the grid from (0, 0) to (xsize, ysize) as an SVG image, a square
+    // per cell, in the colour given by palette (or left out if it gives none).
+    // A path, such as a route found through the grid, is drawn over the top as
+    // a line through each point.
+    pub fn write_svg(&self, w: &mut impl Write, palette: impl Fn(&T) -> Option<Colour>, path: Option<&[Point]>) -> io::Result<()> {
+        writeln!(w, r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}" width="{}" height="{}" shape-rendering="crispEdges">"#,
+            self.xsize, self.ysize, self.xsize * SVG_SCALE, self.ysize * SVG_SCALE)?;
+
+        for y in 0..self.ysize {
+            for x in 0..self.xsize {
+                if let Some(colour) = palette(&self.get(&Point::new(x, y))) {
+                    writeln!(w, r#"<rect x="{}" y="{}" width="1" height="1" fill="{}"/>"#, x, y, colour)?;
+                }
+            }
+        }
+
+        if let Some(path) = path {
+            let points: Vec<String> = path.iter().map(|p| format!("{},{}", p.x as f64 + 0.5, p.y as f64 + 0.5)).collect();
+            writeln!(w, r#"<polyline points="{}" fill="none" stroke="red" stroke-width="0.3" stroke-linejoin="round" shape-rendering="auto"/>"#, points.join(" "))?;
+        }
+
+        writeln!(w, "</svg>")
+    }
+}
+
+// Grids are equal when they're the same size and have the same points set to
+// the same values, however much room their cells take up.
+impl<T: Clone + Default + PartialEq> PartialEq for Grid<T> {
+    fn eq(&self, other: &Grid<T>) -> bool {
+        self.xsize == other.xsize && self.ysize == other.ysize
+            && self.values().count() == other.values().count()
+            && self.values().all(|(p, v)| other.cell(&p) == Some(v))
+    }
+}
+
+impl<T: Clone + Default + Eq> Eq for Grid<T> {}
+
+// Each row from 0 to ysize on a line, with the cells side by side.
+impl<T: Clone + Default + Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.ysize {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..self.xsize {
+                write!(f, "{}", self.get(&Point::new(x, y)))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Clone + Default + Add<Output = T> + From<u8>> Grid<T> {
+    // As set, to one more than it was.
     pub fn increment(&mut self, p: &Point) {
-        if !self.grid.contains_key(p) {
-            self.grid.insert(*p, 0);
+        let v = self.get(p);
+        self.set(p, v + T::from(1));
+    }
+}
+
+// The points of a grid that have been set, row by row.
+pub struct Points<'a, T> {
+    grid: &'a Grid<T>,
+    next: usize
+}
+
+impl<'a, T: Clone + Default> Iterator for Points<'a, T> {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        while let Some(cell) = self.grid.cells.get(self.next) {
+            let i = self.next as i64;
+            self.next += 1;
+
+            if cell.is_some() {
+                return Some(Point::new(self.grid.xmin + i % self.grid.width, self.grid.ymin + i / self.grid.width));
+            }
         }
 
-        let p = self.grid.get_mut(p).unwrap();
-        *p += 1;
+        None
     }
+}
+
+// The neighbours of a point that have been set.
+pub struct Neighbours<'a, T> {
+    grid: &'a Grid<T>,
+    centre: Point,
+    offsets: Iter<'static, (i64, i64)>
+}
+
+impl<'a, T: Clone + Default> Iterator for Neighbours<'a, T> {
+    type Item = Point;
 
-    pub fn get(&self, p: &Point) -> u32 {
-        return match self.grid.get(p) {
-            Some(v) => *v,
-            None => 0
+    fn next(&mut self) -> Option<Point> {
+        for (dx, dy) in &mut self.offsets {
+            let p = Point::new(self.centre.x + dx, self.centre.y + dy);
+            if self.grid.contains(&p) {
+                return Some(p);
+            }
         }
+
+        None
+    }
+}
+
+// A grid that only stores the points that have been set, for when they're
+// few and far between, such as dots on a sheet of paper. Points can be
+// anywhere, even at negative coordinates.
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T = u32> {
+    xsize: i64,
+    ysize: i64,
+    grid: HashMap<Point, T>
+}
+
+impl<T: Clone + Default> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid { xsize: 0, ysize: 0, grid: HashMap::new() }
+    }
+
+    pub fn from_points(points: &[Point], value: T) -> SparseGrid<T> {
+        let mut grid = SparseGrid::new();
+        for p in points {
+            grid.set(p, value.clone());
+        }
+        grid
+    }
+
+    // The points that have been set, row by row.
+    pub fn points(&self) -> Vec<Point> {
+        let mut p: Vec<Point> = self.grid.keys().copied().collect();
+        p.sort();
+        p
+    }
+
+    pub fn xsize(&self) -> i64 {
+        self.xsize
+    }
+
+    pub fn ysize(&self) -> i64 {
+        self.ysize
+    }
+
+    pub fn set(&mut self, p: &Point, val: T) {
+        if p.x >= self.xsize { self.xsize = p.x + 1 }
+        if p.y >= self.ysize { self.ysize = p.y + 1 }
+        self.grid.insert(*p, val);
+    }
+
+    // The value at p, or the default if it hasn't been set.
+    pub fn get(&self, p: &Point) -> T {
+        self.grid.get(p).cloned().unwrap_or_default()
     }
 
-    pub fn do_each(&mut self, f: &dyn Fn(u32) -> u32) {
+    pub fn do_each(&mut self, f: &dyn Fn(T) -> T) {
         for v in self.grid.values_mut() {
-            *v = f(*v);
+            *v = f(v.clone());
         }
     }
 
-    // Counts the number of grid squares for which the predicate is true.
-    pub fn count(&self, f: &dyn Fn(u32) -> bool) -> u32 {
-        let mut count = 0;
+    // Counts the number of points set for which the predicate is true.
+    pub fn count(&self, f: &dyn Fn(T) -> bool) -> u32 {
+        self.grid.values().filter(|v| f((*v).clone())).count() as u32
+    }
+
+    // As Grid::render. Points that haven't been set are empty.
+    pub fn render(&self, draw: impl Fn(&T) -> Option<char>, options: &RenderOptions) -> String {
+        let xmin = self.grid.keys().map(|p| p.x).min().unwrap_or(0).min(0);
+        let ymin = self.grid.keys().map(|p| p.y).min().unwrap_or(0).min(0);
+
+        render(xmin..self.xsize, ymin..self.ysize, |p| self.grid.get(&p).and_then(&draw), options)
+    }
+
+    // As Grid::letters.
+    pub fn letters(&self, lit: impl Fn(&T) -> bool) -> Result<String, OcrError> {
+        ocr(&self.grid.iter().filter(|(_, v)| lit(v)).map(|(p, _)| *p).collect::<Vec<_>>())
+    }
+
+    // The same points in a Grid, such as for drawing.
+    pub fn to_grid(&self) -> Grid<T> {
+        let mut grid = Grid::new(self.xsize, self.ysize);
+        for (p, v) in self.grid.iter() {
+            grid.set(p, v.clone());
+        }
+        grid
+    }
+}
+
+impl<T: Clone + Default + Add<Output = T> + From<u8>> SparseGrid<T> {
+    pub fn increment(&mut self, p: &Point) {
+        let v = self.get(p);
+        self.set(p, v + T::from(1));
+    }
+}
+
+impl<T: Clone + Default> Default for SparseGrid<T> {
+    fn default() -> SparseGrid<T> {
+        SparseGrid::new()
+    }
+}
+
+// The block letters puzzles spell out in dots, four wide and six tall...
+const SMALL_FONT: [(char, [&str; 6]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"])
+];
+
+// ...and six wide and ten tall.
+const LARGE_FONT: [(char, [&str; 10]); 15] = [
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"])
+];
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum OcrError {
+    Empty,
+
+    // How tall the dots are, when that's no font we know.
+    Height(i64),
+
+    // The letter that wasn't recognized, drawn out.
+    Unknown(String)
+}
 
-        for v in self.grid.values() {
-            if f(*v) { count += 1; }
+impl Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OcrError::Empty => write!(f, "no dots to read"),
+            OcrError::Height(height) => write!(f, "letters are {} dots tall, expected 6 or 10", height),
+            OcrError::Unknown(glyph) => write!(f, "unknown letter:\n{}", glyph)
         }
-        
-        count
     }
 }
+
+impl error::Error for OcrError {}
+
+// Reads the dots as a line of block letters, e.g. "EFJKZLBL". Letters are
+// told apart by the empty columns between them, so their spacing doesn't matter.
+pub fn ocr(dots: &[Point]) -> Result<String, OcrError> {
+    let xs = dots.iter().map(|p| p.x).min().ok_or(OcrError::Empty)?..dots.iter().map(|p| p.x + 1).max().unwrap_or(0);
+    let ys = dots.iter().map(|p| p.y).min().ok_or(OcrError::Empty)?..dots.iter().map(|p| p.y + 1).max().unwrap_or(0);
+
+    let height = ys.end - ys.start;
+    if height != 6 && height != 10 {
+        return Err(OcrError::Height(height));
+    }
+
+    let dots: HashSet<&Point> = dots.iter().collect();
+    let columns: Vec<Vec<u8>> = xs.map(|x| ys.clone().map(|y| if dots.contains(&Point::new(x, y)) { b'#' } else { b'.' }).collect()).collect();
+
+    let mut s = String::new();
+    for letter in columns.split(|column| !column.contains(&b'#')).filter(|letter| !letter.is_empty()) {
+        let rows: Vec<String> = (0..height as usize).map(|y| letter.iter().map(|column| column[y] as char).collect()).collect();
+
+        let c = if height == 6 { recognize(&SMALL_FONT, &rows) } else { recognize(&LARGE_FONT, &rows) };
+        s.push(c.ok_or_else(|| OcrError::Unknown(rows.join("\n")))?);
+    }
+
+    Ok(s)
+}
+
+fn recognize<const N: usize>(font: &[(char, [&str; N])], rows: &[String]) -> Option<char> {
+    font.iter().find(|(_, glyph)| {
+        // Letters narrower than the font's width have empty columns to trim.
+        let first = (0..glyph[0].len()).find(|&x| glyph.iter().any(|row| row.as_bytes()[x] == b'#')).unwrap_or(0);
+        glyph.iter().zip(rows).all(|(g, r)| g.get(first..first + r.len()) == Some(r.as_str()) && g[first + r.len()..].chars().all(|c| c == '.'))
+    }).map(|(c, _)| *c)
+}
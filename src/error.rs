@@ -0,0 +1,114 @@
+// Errors from parsing input or solving a part.
+
+use std::error;
+use std::fmt::{self, Display};
+
+#[cfg(test)]
+mod test_error {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Outer(Inner);
+
+    #[derive(Debug)]
+    struct Inner;
+
+    impl Display for Outer {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "outer") }
+    }
+
+    impl Display for Inner {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "inner") }
+    }
+
+    impl error::Error for Outer {
+        fn source(&self) -> Option<&(dyn error::Error + 'static)> { Some(&self.0) }
+    }
+
+    impl error::Error for Inner {}
+
+    #[test]
+    fn from_error_keeps_sources() {
+        let e = Error::from(Outer(Inner));
+        assert_eq!(vec!["outer", "inner"], e.chain());
+        assert_eq!("outer: inner", e.to_string());
+    }
+
+    #[test]
+    fn context() {
+        let r: Result<u32, _> = "x".parse::<u32>();
+        let e = r.context("line 3").unwrap_err();
+        assert_eq!(vec!["line 3", "invalid digit found in string"], e.chain());
+
+        let e = None::<u32>.context("missing value").unwrap_err();
+        assert_eq!("missing value", e.to_string());
+    }
+
+    #[test]
+    fn multi_line() {
+        let e = Error::msg("section 2: bad\n --> in.txt:3:5").context("could not parse in.txt").context("day 4");
+        assert_eq!("day 4:\ncould not parse in.txt:\nsection 2: bad\n --> in.txt:3:5", e.to_string());
+    }
+}
+
+// An error, along with the errors that caused it (outermost first).
+#[derive(Debug)]
+pub struct Error {
+    chain: Vec<String>
+}
+
+impl Error {
+    pub fn msg<M: Display>(msg: M) -> Error {
+        Error { chain: vec![msg.to_string()] }
+    }
+
+    // Wraps this error with a message describing what was being done.
+    pub fn context<M: Display>(mut self, msg: M) -> Error {
+        self.chain.insert(0, msg.to_string());
+        self
+    }
+
+    pub fn chain(&self) -> &[String] {
+        &self.chain
+    }
+}
+
+// The chain on one line, outermost first, unless one of the errors spans lines
+// (such as a diagnostic pointing into the input), when each starts a new line.
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let separator = if self.chain.iter().any(|e| e.contains('\n')) { ":\n" } else { ": " };
+        write!(f, "{}", self.chain.join(separator))
+    }
+}
+
+impl<E: error::Error> From<E> for Error {
+    fn from(e: E) -> Error {
+        let mut chain = vec![e.to_string()];
+
+        let mut source = e.source();
+        while let Some(s) = source {
+            chain.push(s.to_string());
+            source = s.source();
+        }
+
+        Error { chain }
+    }
+}
+
+// Adds context to a failed Result or a missing Option value.
+pub trait Context<T> {
+    fn context<M: Display>(self, msg: M) -> Result<T, Error>;
+}
+
+impl<T, E: Into<Error>> Context<T> for Result<T, E> {
+    fn context<M: Display>(self, msg: M) -> Result<T, Error> {
+        self.map_err(|e| e.into().context(msg))
+    }
+}
+
+impl<T> Context<T> for Option<T> {
+    fn context<M: Display>(self, msg: M) -> Result<T, Error> {
+        self.ok_or_else(|| Error::msg(msg))
+    }
+}
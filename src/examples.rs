@@ -0,0 +1,113 @@
+// Example inputs from the puzzle descriptions, with their expected answers:
+//
+//     examples/day12/ex1.txt
+//     examples/day12/ex2.txt
+//     examples/day12/answers.toml
+//
+// The answers file has a section for each example, named after its input:
+//
+//     [ex1]
+//     part1 = 10
+//     part2 = 36
+//
+// A part with no answer isn't run against that example, since some examples
+// only make sense for one part.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::answers::{self, AnswersError};
+use crate::data::Source;
+
+pub const DIR: &str = "examples";
+
+const ANSWERS_FILE: &str = "answers.toml";
+
+#[cfg(test)]
+mod test_load {
+    use super::*;
+
+    // Writes the given files into a fresh examples directory for day 3.
+    fn examples(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-examples-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+
+        let day = day_dir(&dir, 3);
+        fs::create_dir_all(&day).expect("could not create directory!");
+        for (file, contents) in files {
+            fs::write(day.join(file), contents).expect("could not write file!");
+        }
+
+        dir
+    }
+
+    #[test]
+    fn inputs_and_answers() {
+        let dir = examples("load", &[("ex2.txt", "2\n"), ("ex1.txt", "1\n"), ("notes.md", ""), ("answers.toml", "[ex1]\npart1 = 7\n\n[ex2]\npart2 = \"AB\"\n")]);
+        let examples = load(&dir, 3).expect("loading failed!");
+
+        let names: Vec<&str> = examples.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(vec!["ex1", "ex2"], names);
+
+        assert_eq!(Source::File(day_dir(&dir, 3).join("ex1.txt")), examples[0].source);
+        assert_eq!((Some("7"), None), (examples[0].expected(1), examples[0].expected(2)));
+        assert_eq!((None, Some("AB")), (examples[1].expected(1), examples[1].expected(2)));
+    }
+
+    #[test]
+    fn errors() {
+        let dir = examples("errors", &[("ex1.txt", "1\n"), ("answers.toml", "[ex1]\npart1 = 7\n[ex3]\npart1 = 8\n")]);
+        assert_eq!(AnswersError::NoExample(String::from("ex3")), load(&dir, 3).unwrap_err());
+
+        assert!(matches!(load(&dir, 4), Err(AnswersError::Io(..))));
+    }
+}
+
+// One example input, and the answers it should give.
+#[derive(Debug)]
+pub struct Example {
+    pub name: String,
+    pub source: Source,
+    answers: [Option<String>; 2]
+}
+
+impl Example {
+    pub fn expected(&self, part: u32) -> Option<&str> {
+        self.answers[part as usize - 1].as_deref()
+    }
+}
+
+pub fn day_dir(dir: impl AsRef<Path>, day: u32) -> PathBuf {
+    dir.as_ref().join(format!("day{}", day))
+}
+
+// Loads a day's examples from under dir, in order of name.
+pub fn load(dir: impl AsRef<Path>, day: u32) -> Result<Vec<Example>, AnswersError> {
+    let dir = day_dir(dir, day);
+    let file = dir.join(ANSWERS_FILE);
+
+    let s = fs::read_to_string(&file).map_err(|e| AnswersError::Io(file.display().to_string(), e.kind()))?;
+    let mut answers = answers::parse_sections(&s, |name| Some(name.to_string()))?;
+
+    let entries = fs::read_dir(&dir).map_err(|e| AnswersError::Io(dir.display().to_string(), e.kind()))?;
+
+    let mut examples = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| AnswersError::Io(dir.display().to_string(), e.kind()))?.path();
+        if path.extension().is_none_or(|ext| ext != "txt") {
+            continue;
+        }
+
+        let name = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+        let answers = [answers.remove(&(name.clone(), 1)), answers.remove(&(name.clone(), 2))];
+        examples.push(Example { name, source: Source::File(path), answers });
+    }
+
+    // Anything left over is an answer for an example that isn't there.
+    if let Some(((name, _), _)) = answers.into_iter().next() {
+        return Err(AnswersError::NoExample(name));
+    }
+
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(examples)
+}
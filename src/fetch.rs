@@ -0,0 +1,484 @@
+// Downloading puzzle inputs into a local cache.
+//
+// Settings are read from a config file ($AOC_CONFIG, or ~/.config/aoc/config):
+//
+//     # from the session cookie on adventofcode.com
+//     session = 53616c7465645f5f...
+//     endpoint = https://adventofcode.com
+//     cache = data/cache
+//     delay = 5
+//
+// and can be overridden by AOC_SESSION, AOC_ENDPOINT, AOC_CACHE_DIR and AOC_FETCH_DELAY.
+// Inputs are cached as <cache>/<year>/dayN.txt, and a cached input is never downloaded again.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const YEAR: u32 = 2021;
+pub const DEFAULT_ENDPOINT: &str = "https://adventofcode.com";
+pub const DEFAULT_CACHE: &str = "data/cache";
+pub const DEFAULT_DELAY: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = "advent-of-code-2021 input fetcher";
+
+// Records when the last request was made, so the delay holds across runs.
+const LAST_REQUEST_FILE: &str = ".last-request";
+
+#[cfg(test)]
+mod test_config {
+    use super::*;
+
+    #[test]
+    fn file() {
+        let mut config = Config::default();
+        config.apply_file("# comment\nsession = abc\n\nendpoint = http://localhost:8000/\ndelay = 0.5\n").expect("parsing failed!");
+
+        assert_eq!(Some(String::from("abc")), config.session);
+        assert_eq!("http://localhost:8000/", config.endpoint);
+        assert_eq!(Duration::from_millis(500), config.delay);
+        assert_eq!(PathBuf::from(DEFAULT_CACHE), config.cache);
+    }
+
+    #[test]
+    fn env_overrides_file() {
+        let mut config = Config::default();
+        config.apply_file("session = abc\ncache = /tmp/a").expect("parsing failed!");
+        config.apply_env(|k| if k == "AOC_SESSION" { Some(String::from("xyz")) } else { None }).expect("parsing failed!");
+
+        assert_eq!(Some(String::from("xyz")), config.session);
+        assert_eq!(PathBuf::from("/tmp/a"), config.cache);
+    }
+
+    #[test]
+    fn errors() {
+        let mut config = Config::default();
+        assert_eq!(Err(FetchError::Config(2)), config.apply_file("session = abc\nsession abc"));
+        assert_eq!(Err(FetchError::Config(1)), config.apply_file("colour = blue"));
+        assert_eq!(Err(FetchError::Setting(String::from("delay"), String::from("soon"))), config.apply_file("delay = soon"));
+    }
+
+    #[test]
+    fn paths() {
+        let config = Config { cache: PathBuf::from("cache"), ..Config::default() };
+        assert_eq!(PathBuf::from("cache/2021/day5.txt"), config.cache_file(2021, 5));
+        assert_eq!("https://adventofcode.com/2021/day/5/input", config.url(2021, 5));
+    }
+}
+
+#[cfg(test)]
+mod test_fetch {
+    use super::*;
+    use std::net::TcpListener;
+    use std::ops::{Deref, DerefMut};
+    use std::sync::mpsc;
+    use std::time::Instant;
+
+    const OK: &str = "HTTP/1.1 200 OK\r\nContent-Length: 6\r\n\r\n1\n2\n3\n";
+    const CHUNKED: &str = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n1\n2\n\r\n2;ext=1\r\n3\n\r\n0\r\n\r\n";
+
+    // A stand-in server that answers each connection with the next response,
+    // passing on the requests it was sent.
+    fn serve(responses: Vec<&'static str>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind failed!");
+        let endpoint = format!("http://{}", listener.local_addr().expect("no address!"));
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().expect("accept failed!");
+
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                while !String::from_utf8_lossy(&request).contains("\r\n\r\n") {
+                    let n = stream.read(&mut buf).expect("read failed!");
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                }
+
+                let _ = tx.send(String::from_utf8_lossy(&request).into_owned());
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        (endpoint, rx)
+    }
+
+    // A config with its own cache directory, which is removed again when the
+    // test is over, whether or not it passed.
+    struct TestConfig(Config);
+
+    impl Deref for TestConfig {
+        type Target = Config;
+
+        fn deref(&self) -> &Config {
+            &self.0
+        }
+    }
+
+    impl DerefMut for TestConfig {
+        fn deref_mut(&mut self) -> &mut Config {
+            &mut self.0
+        }
+    }
+
+    impl Drop for TestConfig {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0.cache);
+        }
+    }
+
+    fn config(name: &str, endpoint: &str) -> TestConfig {
+        let cache = env::temp_dir().join(format!("aoc-fetch-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&cache);
+
+        TestConfig(Config { session: Some(String::from("token")), endpoint: endpoint.to_string(), cache, delay: Duration::ZERO })
+    }
+
+    #[test]
+    fn downloads_then_caches() {
+        let (endpoint, requests) = serve(vec![OK]);
+        let config = config("caches", &endpoint);
+
+        let fetched = fetch(&config, 2021, 5).expect("fetch failed!");
+        assert_eq!(Fetched::Downloaded(config.cache_file(2021, 5)), fetched);
+        assert_eq!("1\n2\n3\n", fs::read_to_string(fetched.path()).expect("not cached!"));
+
+        let request = requests.recv().expect("no request!");
+        assert!(request.starts_with("GET /2021/day/5/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=token\r\n"));
+
+        // The server only answers once, so this must come from the cache.
+        assert_eq!(Fetched::Cached(config.cache_file(2021, 5)), fetch(&config, 2021, 5).expect("fetch failed!"));
+    }
+
+    #[test]
+    fn chunked() {
+        let (endpoint, _requests) = serve(vec![CHUNKED, "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n9\r\n1\n2\n"]);
+        let config = config("chunked", &endpoint);
+
+        let fetched = fetch(&config, 2021, 5).expect("fetch failed!");
+        assert_eq!("1\n2\n3\n", fs::read_to_string(fetched.path()).expect("not cached!"));
+
+        let e = fetch(&config, 2021, 6).unwrap_err();
+        assert!(matches!(e, FetchError::Io(_, io::ErrorKind::InvalidData)));
+        assert!(!config.cache_file(2021, 6).exists());
+    }
+
+    #[test]
+    fn server_errors() {
+        let (endpoint, _requests) = serve(vec!["HTTP/1.1 404 Not Found\r\n\r\nPlease don't repeatedly request this endpoint before it unlocks!\n"]);
+        let config = config("errors", &endpoint);
+
+        let e = fetch(&config, 2021, 25).unwrap_err();
+        assert_eq!(FetchError::Http(404, String::from("Please don't repeatedly request this endpoint before it unlocks!")), e);
+        assert!(!config.cache_file(2021, 25).exists());
+    }
+
+    #[test]
+    fn needs_session() {
+        let mut config = config("session", "http://127.0.0.1:1");
+        config.session = None;
+        assert_eq!(Err(FetchError::NoSession), fetch(&config, 2021, 1));
+    }
+
+    #[test]
+    fn waits_between_requests() {
+        let (endpoint, _requests) = serve(vec![OK, OK]);
+        let mut config = config("delay", &endpoint);
+        config.delay = Duration::from_millis(300);
+
+        fetch(&config, 2021, 1).expect("fetch failed!");
+        let start = Instant::now();
+        fetch(&config, 2021, 2).expect("fetch failed!");
+
+        assert!(start.elapsed() >= Duration::from_millis(250));
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum FetchError {
+    Io(String, io::ErrorKind),
+
+    // A line of the config file that isn't a known key = value.
+    Config(usize),
+    Setting(String, String),
+    NoSession,
+    Endpoint(String),
+    Http(u16, String),
+    Curl(String)
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::Io(what, kind) => write!(f, "{}: {:?}", what, kind),
+            FetchError::Config(line) => write!(f, "config line {}: expected session, endpoint, cache or delay = value", line),
+            FetchError::Setting(key, value) => write!(f, "'{}' is not a valid value for {}", value, key),
+            FetchError::NoSession => write!(f, "no session token (set AOC_SESSION, or session in the config file)"),
+            FetchError::Endpoint(url) => write!(f, "'{}' is not an http:// or https:// URL", url),
+            FetchError::Http(status, message) => write!(f, "server returned {}: {}", status, message),
+            FetchError::Curl(message) => write!(f, "curl failed: {}", message)
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Config {
+    pub session: Option<String>,
+    pub endpoint: String,
+
+    // Inputs are kept in a directory per year under here.
+    pub cache: PathBuf,
+
+    // Least time to leave between requests to the endpoint.
+    pub delay: Duration
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config { session: None, endpoint: String::from(DEFAULT_ENDPOINT), cache: PathBuf::from(DEFAULT_CACHE), delay: DEFAULT_DELAY }
+    }
+}
+
+impl Config {
+    // The defaults, then the config file if there is one, then the environment.
+    pub fn load() -> Result<Config, FetchError> {
+        let mut config = Config::default();
+
+        let explicit = env::var("AOC_CONFIG").ok().map(PathBuf::from);
+        let file = explicit.clone().or_else(|| env::var("HOME").ok().map(|h| Path::new(&h).join(".config/aoc/config")));
+
+        if let Some(file) = file {
+            match fs::read_to_string(&file) {
+                Ok(s) => config.apply_file(&s)?,
+
+                // Only a config file that was asked for has to exist.
+                Err(e) if e.kind() == io::ErrorKind::NotFound && explicit.is_none() => (),
+                Err(e) => return Err(FetchError::Io(format!("could not read {}", file.display()), e.kind()))
+            }
+        }
+
+        config.apply_env(|k| env::var(k).ok())?;
+        Ok(config)
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), FetchError> {
+        match key {
+            "session" => self.session = Some(value.to_string()),
+            "endpoint" => self.endpoint = value.to_string(),
+            "cache" => self.cache = PathBuf::from(value),
+            "delay" => {
+                self.delay = value.parse().ok().and_then(|t| Duration::try_from_secs_f64(t).ok())
+                    .ok_or_else(|| FetchError::Setting(key.to_string(), value.to_string()))?;
+            },
+            _ => unreachable!("unknown setting {}", key)
+        }
+
+        Ok(())
+    }
+
+    // Applies the settings in a config file of key = value lines.
+    pub fn apply_file(&mut self, s: &str) -> Result<(), FetchError> {
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match line.split_once('=').map(|(k, v)| (k.trim(), v.trim())) {
+                Some((key @ ("session" | "endpoint" | "cache" | "delay"), value)) => self.set(key, value)?,
+                _ => return Err(FetchError::Config(i + 1))
+            }
+        }
+
+        Ok(())
+    }
+
+    // Applies any settings given by environment variables, looked up with var.
+    pub fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), FetchError> {
+        for (name, key) in [("AOC_SESSION", "session"), ("AOC_ENDPOINT", "endpoint"), ("AOC_CACHE_DIR", "cache"), ("AOC_FETCH_DELAY", "delay")] {
+            if let Some(value) = var(name) {
+                self.set(key, value.trim())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn cache_file(&self, year: u32, day: u32) -> PathBuf {
+        self.cache.join(year.to_string()).join(format!("day{}.txt", day))
+    }
+
+    pub fn url(&self, year: u32, day: u32) -> String {
+        format!("{}/{}/day/{}/input", self.endpoint.trim_end_matches('/'), year, day)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf)
+}
+
+impl Fetched {
+    pub fn path(&self) -> &Path {
+        match self {
+            Fetched::Cached(p) | Fetched::Downloaded(p) => p
+        }
+    }
+}
+
+// Gets a day's input into the cache, downloading it only if it isn't there already.
+pub fn fetch(config: &Config, year: u32, day: u32) -> Result<Fetched, FetchError> {
+    let path = config.cache_file(year, day);
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let session = config.session.as_deref().ok_or(FetchError::NoSession)?;
+
+    let dir = path.parent().unwrap_or(&config.cache);
+    fs::create_dir_all(dir).map_err(|e| FetchError::Io(format!("could not create {}", dir.display()), e.kind()))?;
+
+    wait_for_turn(&config.cache.join(LAST_REQUEST_FILE), config.delay)?;
+    let input = get(&config.url(year, day), session)?;
+
+    // Write to a temporary file first, so a failed write can't leave a partial input in the cache.
+    let io_error = |e: io::Error| FetchError::Io(format!("could not write {}", path.display()), e.kind());
+    let partial = path.with_extension("part");
+    fs::write(&partial, input).map_err(io_error)?;
+    fs::rename(&partial, &path).map_err(io_error)?;
+
+    Ok(Fetched::Downloaded(path))
+}
+
+fn now() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+// Sleeps until at least delay has passed since the last request, then records this one.
+fn wait_for_turn(file: &Path, delay: Duration) -> Result<(), FetchError> {
+    let last = fs::read_to_string(file).ok().and_then(|s| s.trim().parse().ok()).map(Duration::from_millis);
+
+    if let Some(wait) = last.and_then(|last| (last + delay).checked_sub(now())) {
+        thread::sleep(wait);
+    }
+
+    fs::write(file, now().as_millis().to_string()).map_err(|e| FetchError::Io(format!("could not write {}", file.display()), e.kind()))
+}
+
+fn get(url: &str, session: &str) -> Result<String, FetchError> {
+    if let Some(rest) = url.strip_prefix("http://") {
+        http_get(rest, session)
+    }
+    else if url.starts_with("https://") {
+        curl_get(url, session)
+    }
+    else {
+        Err(FetchError::Endpoint(url.to_string()))
+    }
+}
+
+// A plain HTTP/1.1 GET, reading until the server closes the connection. The
+// body may come back chunked, since any HTTP/1.1 server is allowed to do that.
+fn http_get(host_and_path: &str, session: &str) -> Result<String, FetchError> {
+    let (host, path) = match host_and_path.find('/') {
+        Some(i) => host_and_path.split_at(i),
+        None => (host_and_path, "/")
+    };
+    let address = if host.contains(':') { host.to_string() } else { format!("{}:80", host) };
+
+    let io_error = |e: io::Error| FetchError::Io(format!("could not fetch from {}", host), e.kind());
+
+    let mut stream = TcpStream::connect(&address).map_err(io_error)?;
+    stream.set_read_timeout(Some(Duration::from_secs(30))).map_err(io_error)?;
+
+    write!(stream, "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nCookie: session={}\r\nConnection: close\r\n\r\n", path, host, USER_AGENT, session).map_err(io_error)?;
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).map_err(io_error)?;
+
+    let split = response.windows(4).position(|w| w == b"\r\n\r\n").map_or(response.len(), |i| i + 4);
+    let (head, body) = response.split_at(split);
+    let head = String::from_utf8_lossy(head);
+    let status = head.split_whitespace().nth(1).and_then(|s| s.parse().ok()).unwrap_or(0);
+
+    let chunked = head.lines().skip(1).filter_map(|line| line.split_once(':')).any(|(name, value)| {
+        name.trim().eq_ignore_ascii_case("transfer-encoding") && value.to_ascii_lowercase().contains("chunked")
+    });
+    let body = if chunked {
+        dechunk(body).ok_or_else(|| FetchError::Io(format!("bad chunked response from {}", host), io::ErrorKind::InvalidData))?
+    }
+    else {
+        body.to_vec()
+    };
+    let body = String::from_utf8_lossy(&body);
+
+    if status == 200 {
+        Ok(body.to_string())
+    }
+    else {
+        Err(FetchError::Http(status, body.lines().next().unwrap_or("").trim().to_string()))
+    }
+}
+
+// Joins up the chunks of a chunked body, ignoring any extensions and trailers.
+// None if the chunks are malformed or the body stops short.
+fn dechunk(mut body: &[u8]) -> Option<Vec<u8>> {
+    let mut data = Vec::new();
+
+    loop {
+        let end = body.windows(2).position(|w| w == b"\r\n")?;
+        let line = std::str::from_utf8(&body[..end]).ok()?;
+        let size = usize::from_str_radix(line.split(';').next()?.trim(), 16).ok()?;
+        body = &body[end + 2..];
+
+        if size == 0 {
+            return Some(data);
+        }
+        if body.len() < size || !body[size..].starts_with(b"\r\n") {
+            return None;
+        }
+
+        data.extend_from_slice(&body[..size]);
+        body = &body[size + 2..];
+    }
+}
+
+// HTTPS goes through curl. The session cookie is passed on stdin so it doesn't
+// show up in the process list.
+fn curl_get(url: &str, session: &str) -> Result<String, FetchError> {
+    let io_error = |e: io::Error| FetchError::Io(String::from("could not run curl"), e.kind());
+
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--user-agent", USER_AGENT, "--header", "@-", "--write-out", "\n%{http_code}", url])
+        .stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped())
+        .spawn().map_err(io_error)?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "Cookie: session={}", session).map_err(io_error)?;
+    }
+
+    let output = child.wait_with_output().map_err(io_error)?;
+    if !output.status.success() {
+        return Err(FetchError::Curl(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+
+    match status.trim().parse() {
+        Ok(200) => Ok(format!("{}\n", body.trim_end_matches('\n'))),
+        Ok(status) => Err(FetchError::Http(status, body.lines().next().unwrap_or("").trim().to_string())),
+        Err(_) => Err(FetchError::Curl(format!("unexpected output '{}'", status.trim())))
+    }
+}
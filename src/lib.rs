@@ -1,23 +1,391 @@
+pub mod answers;
+pub mod bench;
 pub mod data;
+pub mod diagnostic;
 pub mod drawing;
+pub mod error;
+pub mod examples;
+pub mod fetch;
+pub mod memory;
+pub mod perf;
 
-use std::time::Instant;
-use std::fmt::Display;
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+use std::fmt::{Display, Write};
+
+use answers::{Answers, Status};
+use data::Source;
+
+pub use error::{Context, Error};
+
+#[cfg(test)]
+mod test_timeout {
+    use super::*;
+
+    struct Panics;
+
+    impl Solution for Panics {
+        type Input = ();
+        type Output1 = u32;
+        type Output2 = u32;
+
+        const DAY: u32 = 99;
+        const PART1: &'static str = "panics";
+        const PART2: &'static str = "answer";
+
+        fn parse(_: &str) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn part1(_: &()) -> Result<u32, Error> {
+            panic!("boom")
+        }
+
+        fn part2(_: &()) -> Result<u32, Error> {
+            Ok(42)
+        }
+    }
+
+    #[test]
+    fn finishes() {
+        assert_eq!(Some(42), with_timeout(Duration::from_secs(10), || 42).map(Result::unwrap));
+    }
+
+    #[test]
+    fn overruns() {
+        assert!(with_timeout(Duration::from_millis(10), || thread::sleep(Duration::from_secs(1))).is_none());
+    }
+
+    #[test]
+    fn panicking_part_fails() {
+        for timeout in [None, Some(Duration::from_secs(10))] {
+            let options = Options { timeout, ..Options::default() };
+            let mut out = String::new();
+
+            let (status, time) = run_part::<Panics>(&mut out, "day99", &Arc::new(()), 1, None, &options);
+            assert_eq!((Status::Failed(String::from("panicked: boom")), None), (status, time));
+            assert!(out.starts_with("day99:part1") && out.contains("FAILED") && out.ends_with("panicked: boom\n"), "{}", out);
+
+            // The other part still runs.
+            let (status, _) = run_part::<Panics>(&mut out, "day99", &Arc::new(()), 2, Some("42"), &options);
+            assert_eq!(Status::Pass, status);
+        }
+    }
+}
+
+// A puzzle solution. The input is parsed once and then shared by both parts.
+pub trait Solution: 'static {
+    // Shared with the thread a part runs on when it has a time limit.
+    type Input: Send + Sync + 'static;
+    type Output1: Display;
+    type Output2: Display;
+
+    const DAY: u32;
+
+    // Descriptions of each part's result.
+    const PART1: &'static str;
+    const PART2: &'static str;
+
+    // Time limit for each part, unless one is given on the command line.
+    const TIMEOUT: Option<Duration> = None;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+    fn part1(input: &Self::Input) -> Result<Self::Output1, Error>;
+    fn part2(input: &Self::Input) -> Result<Self::Output2, Error>;
+}
+
+// Options controlling how a day is run.
+#[derive(Default, PartialEq, Eq, Debug)]
+pub struct Options {
+    // Only run this part, if given.
+    pub part: Option<u32>,
+
+    // Benchmark each part rather than timing a single run.
+    pub bench: Option<bench::Bench>,
+
+    // Read input from this path ("-" for stdin) rather than the day's usual input.
+    pub input: Option<String>,
+
+    // Number of days to run at once (default: one per CPU, or one when benchmarking).
+    pub threads: Option<usize>,
+
+    // Time limit for each part, overriding the day's own limit.
+    pub timeout: Option<Duration>,
+
+    // Report what each part allocates. The runner must install memory::Counting.
+    pub memory: bool,
+
+    // Don't add this run's timings to the performance history.
+    pub no_history: bool,
+
+    // Name to record this run under in the performance history.
+    pub label: Option<String>,
+
+    // Run the day's examples, checking their answers, rather than its input.
+    pub examples: bool
+}
+
+// A registered day, as listed and run by the aoc runner.
+pub struct Day {
+    pub day: u32,
+    pub part1: &'static str,
+    pub part2: &'static str,
+    pub run: fn(&Options, &Answers) -> Report
+}
+
+// The outcome of running a day. Rows are collected rather than printed
+// so that days run in parallel can be printed in order.
+#[derive(Default, Debug)]
+pub struct Report {
+    pub day: u32,
+    pub output: String,
+
+    // The status of each part run.
+    pub statuses: Vec<Status>,
+
+    // The time taken by each part that ran to completion, by part number.
+    pub timings: Vec<(u32, f64)>,
+
+    // Time taken to parse the input and run the parts, in seconds.
+    pub time: f64
+}
+
+impl Day {
+    pub const fn new<S: Solution>() -> Day {
+        Day { day: S::DAY, part1: S::PART1, part2: S::PART2, run: run::<S> }
+    }
+}
+
+// Times f, writing its result and how it compares to the expected answer,
+// or FAILED and the error if f fails. If memory profiling is enabled,
+// what f allocated is written after the timing.
+// Returns the status and the time taken in seconds.
+pub fn part<T: Display, E: Display>(out: &mut String, s: &str, descr: &str, f: &dyn Fn() -> Result<T, E>, expected: Option<&str>) -> (Status, f64) {
+    let start = Instant::now();
+
+    let (result, mem) = memory::measure(f);
+
+    let elapsed = start.elapsed().as_secs_f64();
+    let mem = if memory::enabled() { format!("  {}", mem) } else { String::new() };
+
+    let status = match result {
+        Ok(result) => {
+            let status = answers::check(expected, &result.to_string());
+            let _ = writeln!(out, "{:18} {:25} {:<20} {:.4}{}  {}", s, descr, result, elapsed, mem, status);
+            status
+        },
+        Err(e) => {
+            let _ = writeln!(out, "{:18} {:25} {:<20} {:.4}{}  {}", s, descr, "FAILED", elapsed, mem, e);
+            Status::Failed(e.to_string())
+        }
+    };
+
+    (status, elapsed)
+}
+
+// Reads and parses the committed puzzle input for a solution.
+// For use in tests, so panics if that fails.
+pub fn input<S: Solution>() -> S::Input {
+    parse::<S>(&Source::default_for(S::DAY)).unwrap_or_else(|e| panic!("{}", e))
+}
+
+// Parses other input for a solution, usually the puzzle's example text.
+// For use in tests, so panics if that fails.
+pub fn example<'a, S: Solution>(input: impl Into<data::Input<'a>>) -> S::Input {
+    let input = input.into();
+    let name = input.name().unwrap_or_else(|| String::from("example"));
+
+    let s = input.text().unwrap_or_else(|e| panic!("could not read {}: {}", name, e));
+    S::parse(&s).unwrap_or_else(|e| panic!("could not parse {}: {}", name, e))
+}
+
+fn parse<S: Solution>(source: &Source) -> Result<S::Input, Error> {
+    let s = source.read().context(format!("could not read {}", source))?;
+    S::parse(&s).context(format!("could not parse {}", source))
+}
+
+// Parses the input once, then runs both parts (or only the part given) against it.
+pub fn run<S: Solution>(options: &Options, answers: &Answers) -> Report {
+    if options.examples {
+        return run_examples::<S>(options);
+    }
+
+    let source = Source::for_day(S::DAY, options.input.as_deref());
+
+    // The known answers are only for the committed input,
+    // and only its timings are kept so that runs stay comparable.
+    let committed = source == Source::default_for(S::DAY);
+    let empty = Answers::default();
+    let answers = if committed { answers } else { &empty };
 
-pub fn part<T: Display>(s: &str, descr: &str, f: &dyn Fn() -> T) {
     let start = Instant::now();
+    let mut report = Report { day: S::DAY, ..Report::default() };
+
+    let parts: Vec<(u32, Option<&str>)> = (1..=2).filter(|&n| options.part.is_none_or(|p| p == n)).map(|n| (n, answers.get(S::DAY, n))).collect();
+    let timings = run_input::<S>(&mut report, &format!("day{}", S::DAY), &source, &parts, options);
 
-    let result = f();
+    if committed {
+        report.timings = timings;
+    }
 
-    println!("{:15} {:25} {:<20} {:.4}", s, descr, result, start.elapsed().as_secs_f32());
+    report.time = start.elapsed().as_secs_f64();
+    report
 }
 
-#[macro_export]
-macro_rules! solution {
-    ($day:expr, $p1:expr, $p2:expr) => {
-        {
-            aoc::part(&format!("day{}:part1", $day), $p1, &part1);
-            aoc::part(&format!("day{}:part2", $day), $p2, &part2);
+// Runs each of the day's examples in turn, checking the answers given for them.
+// A part with no answer for an example is skipped, and no timings are kept.
+fn run_examples<S: Solution>(options: &Options) -> Report {
+    let start = Instant::now();
+    let mut report = Report { day: S::DAY, ..Report::default() };
+
+    match examples::load(examples::DIR, S::DAY) {
+        Ok(examples) => {
+            for example in examples {
+                let parts: Vec<(u32, Option<&str>)> = (1..=2).filter(|&n| options.part.is_none_or(|p| p == n))
+                    .filter_map(|n| example.expected(n).map(|e| (n, Some(e)))).collect();
+
+                run_input::<S>(&mut report, &format!("day{}:{}", S::DAY, example.name), &example.source, &parts, options);
+            }
+        },
+        Err(e) => {
+            let _ = writeln!(report.output, "{:18} {:25} {:<20} {:6}  {}", format!("day{}", S::DAY), "", "FAILED", "", e);
+            report.statuses.push(Status::Failed(e.to_string()));
+        }
+    }
+
+    report.time = start.elapsed().as_secs_f64();
+    report
+}
+
+// Runs a solution against its examples, as `aoc run N --examples` does.
+// For use in tests, so panics unless there are examples and every answer is right.
+pub fn check_examples<S: Solution>() {
+    let report = run_examples::<S>(&Options::default());
+
+    if report.statuses.is_empty() || report.statuses.iter().any(|s| *s != Status::Pass) {
+        panic!("day {} examples did not all pass:\n{}", S::DAY, report.output);
+    }
+}
+
+// Parses the input once, then runs each of the given parts against it, checking
+// them against the expected answers. Rows are named after name, and added to the
+// report along with each part's status. Returns the times of the parts that completed.
+fn run_input<S: Solution>(report: &mut Report, name: &str, source: &Source, parts: &[(u32, Option<&str>)], options: &Options) -> Vec<(u32, f64)> {
+    let start = Instant::now();
+
+    let input = parse::<S>(source);
+
+    let elapsed = start.elapsed().as_secs_f32();
+    let s = format!("{}:parse", name);
+
+    let input = match input {
+        Ok(input) => {
+            let _ = writeln!(report.output, "{:18} {:25} {:<20} {:.4}", s, "", "", elapsed);
+            input
+        },
+        Err(e) => {
+            let _ = writeln!(report.output, "{:18} {:25} {:<20} {:.4}  {}", s, "", "FAILED", elapsed, e);
+            report.statuses.push(Status::Failed(e.to_string()));
+            return Vec::new();
         }
     };
+
+    let input = Arc::new(input);
+    let mut timings = Vec::new();
+
+    for &(n, expected) in parts {
+        let (status, time) = run_part::<S>(&mut report.output, name, &input, n, expected, options);
+
+        if let Some(time) = time {
+            timings.push((n, time));
+        }
+        report.statuses.push(status);
+    }
+
+    timings
+}
+
+// Runs part n, giving up on it if it's still running after the time limit.
+// Returns the part's status, and its time if it completed.
+fn run_part<S: Solution>(out: &mut String, name: &str, input: &Arc<S::Input>, n: u32, expected: Option<&str>, options: &Options) -> (Status, Option<f64>) {
+    let s = format!("{}:part{}", name, n);
+    let descr = if n == 1 { S::PART1 } else { S::PART2 };
+
+    let task = {
+        let input = Arc::clone(input);
+        let s = s.clone();
+        let expected = expected.map(String::from);
+        let bench = options.bench;
+
+        move || {
+            let mut out = String::new();
+            let result = if n == 1 {
+                time_part(&mut out, &s, descr, &|| S::part1(&input), expected.as_deref(), bench.as_ref())
+            }
+            else {
+                time_part(&mut out, &s, descr, &|| S::part2(&input), expected.as_deref(), bench.as_ref())
+            };
+
+            (out, result)
+        }
+    };
+
+    let start = Instant::now();
+    let limit = options.timeout.or(S::TIMEOUT);
+    let result = match limit {
+        None => Some(panic::catch_unwind(AssertUnwindSafe(task))),
+        Some(limit) => with_timeout(limit, task)
+    };
+
+    let (output, (status, time)) = match result {
+        Some(Ok(result)) => result,
+        // Only this part fails, so the rest of the day's rows are still reported.
+        Some(Err(e)) => {
+            let msg = format!("panicked: {}", panic_message(e.as_ref()));
+            let output = format!("{:18} {:25} {:<20} {:.4}  {}\n", s, descr, "FAILED", start.elapsed().as_secs_f32(), msg);
+            (output, (Status::Failed(msg), f64::NAN))
+        },
+        None => {
+            let limit = limit.unwrap_or_default();
+            let output = format!("{:18} {:25} {:<20} {:.4}  gave up after {:?}\n", s, descr, "TIMEOUT", limit.as_secs_f32(), limit);
+            (output, (Status::Timeout, f64::NAN))
+        }
+    };
+
+    out.push_str(&output);
+
+    match status {
+        Status::Failed(_) | Status::Timeout => (status, None),
+        _ => (status, Some(time))
+    }
+}
+
+fn time_part<T: Display>(out: &mut String, s: &str, descr: &str, f: &dyn Fn() -> Result<T, Error>, expected: Option<&str>, bench: Option<&bench::Bench>) -> (Status, f64) {
+    match bench {
+        Some(b) => bench::part(out, s, descr, f, expected, b),
+        None => part(out, s, descr, f, expected)
+    }
+}
+
+// Runs f on its own thread, returning None if it doesn't finish within the limit,
+// or the panic if it panicked. A thread can't be stopped, so one that overruns is
+// left to finish in the background.
+fn with_timeout<T: Send + 'static>(limit: Duration, f: impl FnOnce() -> T + Send + 'static) -> Option<thread::Result<T>> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let _ = tx.send(panic::catch_unwind(AssertUnwindSafe(f)));
+    });
+
+    rx.recv_timeout(limit).ok()
+}
+
+// The message a panic was raised with, if it was given one.
+pub fn panic_message(e: &(dyn Any + Send)) -> String {
+    e.downcast_ref::<&str>().map(|s| s.to_string())
+        .or_else(|| e.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| String::from("panicked"))
 }
@@ -0,0 +1,197 @@
+// Memory profiling: a global allocator that counts allocations.
+//
+// A binary opts in by installing it:
+//
+//     #[global_allocator]
+//     static ALLOCATOR: aoc::memory::Counting = aoc::memory::Counting;
+//
+// and then calling enable(). Counts are kept per thread, so parts run at the
+// same time on different threads don't see each other's allocations.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(test)]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[cfg(test)]
+mod test_measure {
+    use super::*;
+
+    #[test]
+    fn counts_allocations() {
+        enable();
+
+        let (_, stats) = measure(|| {
+            let a: Vec<u8> = Vec::with_capacity(100);
+            let b: Vec<u8> = Vec::with_capacity(50);
+            drop(a);
+            drop(b);
+
+            let c: Vec<u8> = Vec::with_capacity(120);
+            drop(c);
+        });
+
+        assert_eq!(3, stats.allocations);
+        assert_eq!(270, stats.bytes);
+        assert_eq!(150, stats.peak);
+    }
+
+    #[test]
+    fn returns_result() {
+        enable();
+
+        let (v, stats) = measure(|| vec![1u32; 4]);
+
+        assert_eq!(vec![1, 1, 1, 1], v);
+        assert_eq!(1, stats.allocations);
+        assert_eq!(16, stats.bytes);
+    }
+}
+
+#[cfg(test)]
+mod test_bytes {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!("512 B", Bytes(512).to_string());
+        assert_eq!("1.5 KiB", Bytes(1536).to_string());
+        assert_eq!("2.0 MiB", Bytes(2 * 1024 * 1024).to_string());
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// Running counts for the current thread. Live bytes can go negative when a
+// block allocated before measuring started is freed.
+#[derive(Clone, Copy)]
+struct Counts {
+    allocations: usize,
+    bytes: usize,
+    live: isize,
+    peak: isize
+}
+
+impl Counts {
+    const ZERO: Counts = Counts { allocations: 0, bytes: 0, live: 0, peak: 0 };
+}
+
+thread_local! {
+    // Const-initialised and without a destructor, so using it never allocates.
+    static COUNTS: Cell<Counts> = const { Cell::new(Counts::ZERO) };
+}
+
+fn record(allocated: usize, freed: usize, new_allocation: bool) {
+    // Ignore allocations made while the thread is being torn down.
+    let _ = COUNTS.try_with(|c| {
+        let mut counts = c.get();
+
+        if new_allocation {
+            counts.allocations += 1;
+            counts.bytes += allocated;
+        }
+        counts.live += allocated as isize - freed as isize;
+        counts.peak = counts.peak.max(counts.live);
+
+        c.set(counts);
+    });
+}
+
+// Forwards to the system allocator, counting as it goes once enabled.
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if ENABLED.load(Ordering::Relaxed) {
+            record(layout.size(), 0, true);
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        if ENABLED.load(Ordering::Relaxed) {
+            record(layout.size(), 0, true);
+        }
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if ENABLED.load(Ordering::Relaxed) {
+            record(0, layout.size(), false);
+        }
+        System.dealloc(ptr, layout)
+    }
+
+    // A reallocation counts as a new allocation of the new size.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if ENABLED.load(Ordering::Relaxed) {
+            record(new_size, layout.size(), true);
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+// Starts counting. Does nothing useful unless Counting is the global allocator.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+// What a measured call allocated.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Stats {
+    pub allocations: usize,
+
+    // Total bytes allocated, including any since freed.
+    pub bytes: usize,
+
+    // Most bytes allocated and not yet freed at any one time.
+    pub peak: usize
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:>9} allocs {:>10} total {:>10} peak", self.allocations, Bytes(self.bytes), Bytes(self.peak))
+    }
+}
+
+// Calls f, counting the allocations it makes on this thread.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Stats) {
+    let before = COUNTS.with(|c| c.replace(Counts::ZERO));
+
+    let result = f();
+
+    let counts = COUNTS.with(|c| c.replace(before));
+    let stats = Stats { allocations: counts.allocations, bytes: counts.bytes, peak: counts.peak.max(0) as usize };
+
+    (result, stats)
+}
+
+// A number of bytes, displayed in the largest unit that keeps it above 1.
+pub struct Bytes(pub usize);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+        if self.0 < 1024 {
+            return f.pad(&format!("{} B", self.0));
+        }
+
+        let mut n = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while n >= 1024.0 && unit < UNITS.len() - 1 {
+            n /= 1024.0;
+            unit += 1;
+        }
+
+        f.pad(&format!("{:.1} {}", n, UNITS[unit]))
+    }
+}
@@ -0,0 +1,308 @@
+// History of part timings, kept as CSV so that runs can be compared:
+//
+//     timestamp,revision,label,day,part,seconds
+//     1639000000000,3f2a9c1,,5,1,0.0263004
+//
+// Every row from one run shares its timestamp and revision.
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_FILE: &str = "data/perf.csv";
+
+const HEADER: &str = "timestamp,revision,label,day,part,seconds";
+
+#[cfg(test)]
+mod test_parse {
+    use super::*;
+
+    #[test]
+    fn records() {
+        let input = "timestamp,revision,label,day,part,seconds\n10,abc,,1,1,0.5\n10,abc,,1,2,0.25\n20,def,fast,1,1,0.4\n";
+        let history = History::parse(input).expect("parsing failed!");
+
+        assert_eq!(3, history.records.len());
+        assert_eq!(Record { timestamp: 20, revision: String::from("def"), label: String::from("fast"), day: 1, part: 1, seconds: 0.4 }, history.records[2]);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(PerfError::Syntax(2), History::parse("timestamp,revision,label,day,part,seconds\n10,abc,1,1,0.5").unwrap_err());
+        assert_eq!(PerfError::Syntax(1), History::parse("10,abc,,x,1,0.5").unwrap_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let r = Record { timestamp: 10, revision: String::from("abc"), label: String::new(), day: 3, part: 2, seconds: 0.125 };
+        let history = History::parse(&r.to_string()).expect("parsing failed!");
+        assert_eq!(vec![r], history.records);
+    }
+}
+
+#[cfg(test)]
+mod test_compare {
+    use super::*;
+
+    fn record(timestamp: u64, label: &str, day: u32, part: u32, seconds: f64) -> Record {
+        Record { timestamp, revision: format!("rev{}", timestamp), label: String::from(label), day, part, seconds }
+    }
+
+    fn history() -> History {
+        History { records: vec![
+            record(10, "base", 1, 1, 1.0),
+            record(10, "base", 1, 2, 1.0),
+            record(20, "", 1, 1, 2.0),
+            record(20, "", 1, 2, 2.0),
+            record(30, "", 1, 1, 2.1),
+            record(30, "", 1, 2, 1.0),
+            record(30, "", 2, 1, 1.0)
+        ]}
+    }
+
+    #[test]
+    fn runs() {
+        let h = history();
+        let runs = h.runs();
+
+        assert_eq!(3, runs.len());
+        assert_eq!(30, runs[2].timestamp);
+        assert_eq!(3, runs[2].records.len());
+    }
+
+    #[test]
+    fn against_previous() {
+        let h = history();
+        let (latest, previous) = h.latest_and_baseline(None).expect("no runs!");
+        let changes = compare(&latest, &previous);
+
+        assert_eq!(3, changes.len());
+        assert_eq!((1, 1), (changes[0].day, changes[0].part));
+        assert!((changes[0].percent().unwrap() - 5.0).abs() < 1e-9);
+        assert!((changes[1].percent().unwrap() + 50.0).abs() < 1e-9);
+        assert_eq!(None, changes[2].before);
+        assert_eq!(None, changes[2].percent());
+    }
+
+    #[test]
+    fn against_named() {
+        let h = history();
+
+        let (_, base) = h.latest_and_baseline(Some("base")).expect("no runs!");
+        assert_eq!(10, base.timestamp);
+
+        let (_, base) = h.latest_and_baseline(Some("rev20")).expect("no runs!");
+        assert_eq!(20, base.timestamp);
+
+        assert_eq!(None, h.latest_and_baseline(Some("missing")));
+    }
+
+    #[test]
+    fn slower() {
+        let change = Change { day: 1, part: 1, before: Some(1.0), after: 1.2 };
+        assert!(change.slower_than(10.0));
+        assert!(!change.slower_than(25.0));
+
+        let change = Change { day: 1, part: 1, before: Some(0.0), after: 1e-6 };
+        assert_eq!(None, change.percent());
+        assert!(!change.slower_than(10.0));
+    }
+
+    #[test]
+    fn keeps_full_precision() {
+        let r = Record { timestamp: 10, revision: String::from("abc"), label: String::new(), day: 3, part: 2, seconds: 3.25e-7 };
+        let history = History::parse(&r.to_string()).expect("parsing failed!");
+        assert_eq!(3.25e-7, history.records[0].seconds);
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum PerfError {
+    Io(String, io::ErrorKind),
+    Syntax(usize)
+}
+
+impl fmt::Display for PerfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PerfError::Io(file, kind) => write!(f, "could not access {}: {:?}", file, kind),
+            PerfError::Syntax(line) => write!(f, "line {}: expected {}", line, HEADER)
+        }
+    }
+}
+
+// The time taken by one part in one run.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Record {
+    // Milliseconds since the Unix epoch when the run started.
+    pub timestamp: u64,
+    pub revision: String,
+
+    // Optional name for the run, to compare against later.
+    pub label: String,
+
+    pub day: u32,
+    pub part: u32,
+    pub seconds: f64
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // In full, as rounding would lose the differences between the fastest parts.
+        write!(f, "{},{},{},{},{},{}", self.timestamp, self.revision, self.label, self.day, self.part, self.seconds)
+    }
+}
+
+// All the records from one run.
+#[derive(PartialEq, Debug)]
+pub struct Run<'a> {
+    pub timestamp: u64,
+    pub revision: &'a str,
+    pub label: &'a str,
+    pub records: Vec<&'a Record>
+}
+
+impl Run<'_> {
+    fn matches(&self, name: &str) -> bool {
+        self.label == name || (!name.is_empty() && self.revision.starts_with(name))
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct History {
+    pub records: Vec<Record>
+}
+
+impl History {
+    pub fn load(file: &str) -> Result<History, PerfError> {
+        let s = fs::read_to_string(file).map_err(|e| PerfError::Io(file.to_string(), e.kind()))?;
+        History::parse(&s)
+    }
+
+    pub fn parse(s: &str) -> Result<History, PerfError> {
+        let mut records = Vec::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line == HEADER {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split(',').collect();
+            if fields.len() != 6 {
+                return Err(PerfError::Syntax(i + 1));
+            }
+
+            let syntax = |_| PerfError::Syntax(i + 1);
+            records.push(Record {
+                timestamp: fields[0].parse().map_err(syntax)?,
+                revision: fields[1].to_string(),
+                label: fields[2].to_string(),
+                day: fields[3].parse().map_err(syntax)?,
+                part: fields[4].parse().map_err(syntax)?,
+                seconds: fields[5].parse().map_err(|_| PerfError::Syntax(i + 1))?
+            });
+        }
+
+        Ok(History { records })
+    }
+
+    // Adds records to the end of the file, creating it (with a header) if needed.
+    pub fn append(file: &str, records: &[Record]) -> Result<(), PerfError> {
+        let io_error = |e: io::Error| PerfError::Io(file.to_string(), e.kind());
+
+        let new = fs::metadata(file).is_err();
+        let mut f = OpenOptions::new().create(true).append(true).open(file).map_err(io_error)?;
+
+        let mut s = String::new();
+        if new {
+            s.push_str(HEADER);
+            s.push('\n');
+        }
+        for r in records {
+            s.push_str(&r.to_string());
+            s.push('\n');
+        }
+
+        f.write_all(s.as_bytes()).map_err(io_error)
+    }
+
+    // Groups the records into runs, oldest first.
+    pub fn runs(&self) -> Vec<Run<'_>> {
+        let mut runs: Vec<Run> = Vec::new();
+
+        for r in &self.records {
+            match runs.last_mut() {
+                Some(run) if run.timestamp == r.timestamp && run.revision == r.revision => run.records.push(r),
+                _ => runs.push(Run { timestamp: r.timestamp, revision: &r.revision, label: &r.label, records: vec![r] })
+            }
+        }
+
+        runs
+    }
+
+    // The latest run, and the run to compare it with: the latest earlier run with the given
+    // label or revision, or just the one before it.
+    pub fn latest_and_baseline(&self, name: Option<&str>) -> Option<(Run<'_>, Run<'_>)> {
+        let mut runs = self.runs();
+        let latest = runs.pop()?;
+
+        let baseline = match name {
+            Some(name) => {
+                let i = runs.iter().rposition(|r| r.matches(name))?;
+                runs.swap_remove(i)
+            },
+            None => runs.pop()?
+        };
+
+        Some((latest, baseline))
+    }
+}
+
+// How a part's time changed between two runs.
+#[derive(PartialEq, Debug)]
+pub struct Change {
+    pub day: u32,
+    pub part: u32,
+
+    // None if the part wasn't in the baseline run.
+    pub before: Option<f64>,
+    pub after: f64
+}
+
+impl Change {
+    // None if the part wasn't in the baseline, or took no measurable time there.
+    pub fn percent(&self) -> Option<f64> {
+        self.before.filter(|&b| b != 0.0).map(|b| (self.after - b) / b * 100.0)
+    }
+
+    pub fn slower_than(&self, threshold: f64) -> bool {
+        self.percent().is_some_and(|p| p > threshold)
+    }
+}
+
+// Compares each part in the latest run with the same part in the baseline, sorted by day and part.
+pub fn compare(latest: &Run, baseline: &Run) -> Vec<Change> {
+    let mut changes: Vec<Change> = latest.records.iter().map(|r| {
+        let before = baseline.records.iter().find(|b| b.day == r.day && b.part == r.part).map(|b| b.seconds);
+        Change { day: r.day, part: r.part, before, after: r.seconds }
+    }).collect();
+
+    changes.sort_by_key(|c| (c.day, c.part));
+    changes
+}
+
+pub fn timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+// The current git revision, marked if there are uncommitted changes, or "unknown" outside a git checkout.
+pub fn revision() -> String {
+    Command::new("git").args(["describe", "--always", "--dirty"]).output().ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8(o.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|| String::from("unknown"))
+}